@@ -3,14 +3,19 @@
 //
 
 use std::fmt;
-use std::fmt::{Debug, Display};
 use std::io::ErrorKind;
-use std::num::ParseIntError;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub enum Error {
     IndexOfBounds,
+    CapacityOverflow,
+    AllocError(std::alloc::Layout),
+    AlreadyLinked,
+    NotLinked,
+    InvalidExpression,
+    DivideByZero,
+    ArithmeticOverflow,
     ParallelEdges,
     SelfLoop,
     IOError(std::io::Error),
@@ -28,6 +33,13 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::IndexOfBounds => write!(f, "Array Index of bounds!"),
+            Error::CapacityOverflow => write!(f, "Capacity overflow!"),
+            Error::AllocError(layout) => write!(f, "Memory allocation of {} bytes failed!", layout.size()),
+            Error::AlreadyLinked => write!(f, "Element is already linked!"),
+            Error::NotLinked => write!(f, "Element is not linked in this list!"),
+            Error::InvalidExpression => write!(f, "Invalid expression!"),
+            Error::DivideByZero => write!(f, "Divide by zero!"),
+            Error::ArithmeticOverflow => write!(f, "Arithmetic overflow!"),
             Error::IOError(ref e) => write!(f, "{}", e),
             Error::ConvertError(ref e) => write!(f, "{}", e),
             Error::SelfLoop => write!(f, "Self loop is Detected"),
@@ -3,11 +3,9 @@
 //
 
 use std::collections::LinkedList;
-use std::fmt::{Display, Formatter, Write};
-use std::fmt;
+use std::fmt::{self, Write};
 use std::fs::File;
-use std::io::{BufRead, BufReader, ErrorKind};
-use std::iter::FromIterator;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::str::FromStr;
 
@@ -96,7 +94,7 @@ impl AdjacencyMatrix {
 }
 
 fn validate_vertex(vert: usize, a: usize) -> Result<()> {
-    if a < 0 || a > vert {
+    if a > vert {
         return Err(Error::IndexOfBounds);
     }
     Ok(())
@@ -104,21 +102,21 @@ fn validate_vertex(vert: usize, a: usize) -> Result<()> {
 
 impl fmt::Display for AdjacencyMatrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "V={},E={}\n", self.vertex, self.edge);
+        writeln!(f, "V={},E={}", self.vertex, self.edge)?;
         for i in 0..self.vertex {
             for j in 0..self.edge {
-                write!(f, "{}, ", self.matrix[i][j]);
+                write!(f, "{}, ", self.matrix[i][j])?;
             }
-            f.write_char('\n');
+            f.write_char('\n')?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
 
 impl Graph for AdjacencyMatrix {
     fn edge(&self) -> usize {
-        return self.edge;
+        self.edge
     }
 
     fn vertex(&self) -> usize {
@@ -142,7 +140,7 @@ impl Graph for AdjacencyMatrix {
         vec
     }
     fn degree(&self, v: usize) -> usize {
-        return self.adj(v).len();
+        self.adj(v).len()
     }
 }
 
@@ -199,18 +197,17 @@ impl AdjacencyList {
 
 impl fmt::Display for AdjacencyList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "V={},E={}\n", self.vertex, self.edge);
+        writeln!(f, "V={},E={}", self.vertex, self.edge)?;
         for i in 0..self.vertex {
-            write!(f, "{}:{:?}", i, self.list[i]);
-            f.write_char('\n');
+            writeln!(f, "{}:{:?}", i, self.list[i])?;
         }
-        write!(f, "\n")
+        writeln!(f)
     }
 }
 
 impl Graph for AdjacencyList {
     fn edge(&self) -> usize {
-        return self.edge;
+        self.edge
     }
 
     fn vertex(&self) -> usize {
@@ -225,13 +222,13 @@ impl Graph for AdjacencyList {
     fn adj(&self, vertex: usize) -> Vec<usize> {
         validate_vertex(self.vertex, vertex).unwrap();
         self.list[vertex].iter().fold(Vec::new(), |mut arr, value| {
-            arr.push(value.clone());
+            arr.push(*value);
             arr
         })
     }
 
     fn degree(&self, v: usize) -> usize {
-        return self.adj(v).len();
+        self.adj(v).len()
     }
 }
 
@@ -246,4 +243,4 @@ fn test_graph() -> std::result::Result<(), Box<dyn std::error::Error>> {
     let res = AdjacencyList::from_file("g.txt")?;
     println!("{}", res);
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,4 +1,3 @@
-#![feature(alloc_internals)]
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 11:41
 //
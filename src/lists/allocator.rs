@@ -0,0 +1,83 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/22 14:10
+//
+use std::alloc::{self, Layout};
+use std::ptr::{self, NonNull};
+
+/// memory allocator used by containers like `Array`, a stable replacement of `std::alloc::Allocator`
+/// # Safety
+/// memory returned by `allocate` must stay valid until it's passed to `deallocate` or `reallocate`,
+/// and must fit `layout`
+/// ```
+/// use std::alloc::Layout;
+/// use std::cell::Cell;
+/// use std::ptr::NonNull;
+///
+/// use algorithms_rs::lists::allocator::{Allocator, Global};
+/// use algorithms_rs::lists::array::Array;
+///
+/// /// allocator which counts live allocations
+/// #[derive(Default)]
+/// struct Counting {
+///     live: Cell<usize>,
+/// }
+///
+/// unsafe impl Allocator for &Counting {
+///     fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+///         self.live.set(self.live.get() + 1);
+///         Global.allocate(layout)
+///     }
+///
+///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+///         self.live.set(self.live.get() - 1);
+///         Global.deallocate(ptr, layout)
+///     }
+/// }
+///
+/// let counting = Counting::default();
+/// let mut arr = Array::new_in(&counting);
+/// arr.append(5);
+/// assert_eq!(counting.live.get(), 1);
+/// drop(arr);
+/// assert_eq!(counting.live.get(), 0);
+/// ```
+pub unsafe trait Allocator {
+    /// allocate memory for `layout` of non zero size, return `None` if it's failed
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// free memory allocated by this allocator with the same `layout`
+    /// # Safety
+    /// `ptr` must be allocated by this allocator with `layout`
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// resize memory allocated with `layout` to `new_size` bytes of non zero size,
+    /// the content is kept up to the smaller size, return `None` and keep the old memory if it's failed
+    /// the default implementation allocates new memory and copies the content
+    /// # Safety
+    /// `ptr` must be allocated by this allocator with `layout`
+    unsafe fn reallocate(&self, ptr: NonNull<u8>, layout: Layout, new_size: usize) -> Option<NonNull<u8>> {
+        let new_layout = Layout::from_size_align(new_size, layout.align()).ok()?;
+        let new_ptr = self.allocate(new_layout)?;
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), layout.size().min(new_size));
+        self.deallocate(ptr, layout);
+        Some(new_ptr)
+    }
+}
+
+/// the global allocator registered by `#[global_allocator]`, default allocator of containers
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        alloc::dealloc(ptr.as_ptr(), layout)
+    }
+
+    unsafe fn reallocate(&self, ptr: NonNull<u8>, layout: Layout, new_size: usize) -> Option<NonNull<u8>> {
+        NonNull::new(alloc::realloc(ptr.as_ptr(), layout, new_size))
+    }
+}
@@ -0,0 +1,864 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/24 09:30
+//
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+
+use crate::error;
+use crate::lists::double_linked::next_id;
+
+/// handle of element in `ArenaList`, the generation tells a reused slot from the old one,
+/// so a handle of removed element is never mistaken for a new element,
+/// and the id of list tells it from a handle of other list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: usize,
+    generation: u64,
+    owner: usize,
+}
+
+enum Slot<T> {
+    Occupied {
+        value: T,
+        prev: Option<usize>,
+        next: Option<usize>,
+    },
+    Vacant {
+        next_free: Option<usize>,
+    },
+}
+
+struct Entry<T> {
+    generation: u64,
+    slot: Slot<T>,
+}
+
+/// double linked list whose nodes live in one `Vec` and link each other by slot number,
+/// removed slots are reused, so there is no allocation per node once the capacity is enough
+/// ```
+/// use algorithms_rs::lists::arena::ArenaList;
+///
+/// // least recently used key is at the back
+/// let mut lru = ArenaList::with_capacity(3);
+/// let a = lru.push_front("a");
+/// let b = lru.push_front("b");
+/// lru.push_front("c");
+/// assert!(lru.move_to_front(a));
+/// assert_eq!(lru.pop_back(), Some("b"));
+/// assert_eq!(lru.get(b), None);
+/// assert_eq!(lru.iter().copied().collect::<Vec<_>>(), vec!["a", "c"]);
+/// ```
+pub struct ArenaList<T> {
+    entries: Vec<Entry<T>>,
+    free: Option<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    id: usize,
+}
+
+impl<T> ArenaList<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// create list which holds `cap` elements without allocation
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(cap),
+            free: None,
+            head: None,
+            tail: None,
+            len: 0,
+            id: next_id(),
+        }
+    }
+
+    /// return number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// return number of elements can be held without allocation
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    /// drop all elements, the slots are kept for reuse and all handles become invalid
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    fn links(&self, slot: usize) -> (Option<usize>, Option<usize>) {
+        match self.entries[slot].slot {
+            Slot::Occupied { prev, next, .. } => (prev, next),
+            Slot::Vacant { .. } => unreachable!("linked slot is vacant"),
+        }
+    }
+
+    fn set_prev(&mut self, slot: usize, link: Option<usize>) {
+        if let Slot::Occupied { ref mut prev, .. } = self.entries[slot].slot {
+            *prev = link;
+        }
+    }
+
+    fn set_next(&mut self, slot: usize, link: Option<usize>) {
+        if let Slot::Occupied { ref mut next, .. } = self.entries[slot].slot {
+            *next = link;
+        }
+    }
+
+    fn value(&self, slot: usize) -> &T {
+        match self.entries[slot].slot {
+            Slot::Occupied { ref value, .. } => value,
+            Slot::Vacant { .. } => unreachable!("linked slot is vacant"),
+        }
+    }
+
+    fn value_mut(&mut self, slot: usize) -> &mut T {
+        match self.entries[slot].slot {
+            Slot::Occupied { ref mut value, .. } => value,
+            Slot::Vacant { .. } => unreachable!("linked slot is vacant"),
+        }
+    }
+
+    fn handle(&self, slot: usize) -> Index {
+        Index {
+            slot,
+            generation: self.entries[slot].generation,
+            owner: self.id,
+        }
+    }
+
+    /// return slot of `index` if it's still occupied by the same element of this list
+    fn resolve(&self, index: Index) -> Option<usize> {
+        if index.owner != self.id {
+            return None;
+        }
+        match self.entries.get(index.slot) {
+            Some(Entry { generation, slot: Slot::Occupied { .. } }) if *generation == index.generation => Some(index.slot),
+            _ => None,
+        }
+    }
+
+    /// link `slot` between `prev` and `next`, which must be adjacent
+    fn link(&mut self, slot: usize, prev: Option<usize>, next: Option<usize>) {
+        self.set_prev(slot, prev);
+        self.set_next(slot, next);
+        match prev {
+            Some(prev) => self.set_next(prev, Some(slot)),
+            None => self.head = Some(slot),
+        }
+        match next {
+            Some(next) => self.set_prev(next, Some(slot)),
+            None => self.tail = Some(slot),
+        }
+    }
+
+    /// unlink `slot` from its neighbours, the slot is still occupied
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = self.links(slot);
+        match prev {
+            Some(prev) => self.set_next(prev, next),
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.set_prev(next, prev),
+            None => self.tail = prev,
+        }
+    }
+
+    /// put `ele` into a free slot and link it between `prev` and `next`
+    fn insert_between(&mut self, prev: Option<usize>, next: Option<usize>, ele: T) -> Index {
+        let occupied = Slot::Occupied { value: ele, prev: None, next: None };
+        let slot = match self.free {
+            Some(slot) => {
+                if let Slot::Vacant { next_free } = mem::replace(&mut self.entries[slot].slot, occupied) {
+                    self.free = next_free;
+                }
+                slot
+            }
+            None => {
+                self.entries.push(Entry { generation: 0, slot: occupied });
+                self.entries.len() - 1
+            }
+        };
+        self.link(slot, prev, next);
+        self.len += 1;
+        self.handle(slot)
+    }
+
+    /// unlink `slot`, free it and return its element
+    fn remove_slot(&mut self, slot: usize) -> T {
+        self.unlink(slot);
+        self.len -= 1;
+        let entry = &mut self.entries[slot];
+        entry.generation += 1;
+        let vacant = Slot::Vacant { next_free: self.free };
+        self.free = Some(slot);
+        match mem::replace(&mut entry.slot, vacant) {
+            Slot::Occupied { value, .. } => value,
+            Slot::Vacant { .. } => unreachable!("linked slot is vacant"),
+        }
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn push_front(&mut self, ele: T) -> Index {
+        self.insert_between(None, self.head, ele)
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn push_back(&mut self, ele: T) -> Index {
+        self.insert_between(self.tail, None, ele)
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        let slot = self.head?;
+        Some(self.remove_slot(slot))
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        let slot = self.tail?;
+        Some(self.remove_slot(slot))
+    }
+
+    /// same as `pop_back`
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    /// return the first element
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.map(|slot| self.value(slot))
+    }
+
+    /// return the first element as mutable
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        let slot = self.head?;
+        Some(self.value_mut(slot))
+    }
+
+    /// return the last element
+    pub fn peek_back(&self) -> Option<&T> {
+        self.tail.map(|slot| self.value(slot))
+    }
+
+    /// return the last element as mutable
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        let slot = self.tail?;
+        Some(self.value_mut(slot))
+    }
+
+    /// return handle of the first element
+    pub fn front_index(&self) -> Option<Index> {
+        self.head.map(|slot| self.handle(slot))
+    }
+
+    /// return handle of the last element
+    pub fn back_index(&self) -> Option<Index> {
+        self.tail.map(|slot| self.handle(slot))
+    }
+
+    /// return element of `index`, `None` if it's removed
+    pub fn get(&self, index: Index) -> Option<&T> {
+        self.resolve(index).map(|slot| self.value(slot))
+    }
+
+    /// return element of `index` as mutable, `None` if it's removed
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        let slot = self.resolve(index)?;
+        Some(self.value_mut(slot))
+    }
+
+    /// return true if element of `index` is in list
+    pub fn contains_index(&self, index: Index) -> bool {
+        self.resolve(index).is_some()
+    }
+
+    /// remove element of `index`, `None` if it's removed already
+    /// 时间复杂度: O(1)
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        let slot = self.resolve(index)?;
+        Some(self.remove_slot(slot))
+    }
+
+    /// move element of `index` to the front, return false if it's removed
+    /// 时间复杂度: O(1)
+    pub fn move_to_front(&mut self, index: Index) -> bool {
+        match self.resolve(index) {
+            Some(slot) => {
+                self.unlink(slot);
+                self.link(slot, None, self.head);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// move element of `index` to the back, return false if it's removed
+    /// 时间复杂度: O(1)
+    pub fn move_to_back(&mut self, index: Index) -> bool {
+        match self.resolve(index) {
+            Some(slot) => {
+                self.unlink(slot);
+                self.link(slot, self.tail, None);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// iterate elements from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    /// iterate mutable elements from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            entries: self.entries.as_mut_ptr(),
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+            _mark: PhantomData,
+        }
+    }
+
+    /// return true if list contains `ele`
+    pub fn contains(&self, ele: &T) -> bool
+        where T: PartialEq
+    {
+        self.iter().any(|x| x == ele)
+    }
+
+    /// reverse the list in place
+    /// 时间复杂度: O(n)
+    pub fn reverse(&mut self) {
+        let mut cur = self.head;
+        while let Some(slot) = cur {
+            let (prev, next) = self.links(slot);
+            self.set_prev(slot, next);
+            self.set_next(slot, prev);
+            cur = next;
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// move all elements of `other` to the back, `other` becomes empty,
+    /// the elements are moved into this arena so handles of `other` become invalid
+    /// 时间复杂度: O(m)
+    pub fn append(&mut self, other: &mut ArenaList<T>) {
+        self.entries.reserve(other.len.saturating_sub(self.entries.len() - self.len));
+        while let Some(ele) = other.pop_front() {
+            self.push_back(ele);
+        }
+    }
+
+    /// split list into two at `at`, return elements after `at` and keep `[0, at)`,
+    /// the returned elements are moved to new arena so their handles become invalid
+    /// 时间复杂度: O(n - at)
+    /// ```
+    /// use algorithms_rs::lists::arena::ArenaList;
+    /// let mut ls: ArenaList<i32> = (0..5).collect();
+    /// let tail = ls.split_off(3).unwrap();
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(tail.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+    /// assert!(ls.split_off(4).is_err());
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Result<ArenaList<T>, error::Error> {
+        if at > self.len {
+            return Err(error::Error::IndexOfBounds);
+        }
+        let mut other = ArenaList::with_capacity(self.len - at);
+        for _ in at..self.len {
+            let ele = self.pop_back().unwrap();
+            other.push_front(ele);
+        }
+        Ok(other)
+    }
+
+    /// remove all elements matching `pred` and return how many are removed
+    /// 时间复杂度: O(n)
+    pub fn remove_if<F>(&mut self, mut pred: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        let mut removed = 0;
+        let mut cur = self.head;
+        while let Some(slot) = cur {
+            cur = self.links(slot).1;
+            if pred(self.value(slot)) {
+                self.remove_slot(slot);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// return cursor at the first element, or at the "ghost" position if list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// return cursor at the last element, or at the "ghost" position if list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for ArenaList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArenaList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArenaList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArenaList<T> {}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut ls = ArenaList::new();
+        ls.extend(iter);
+        ls
+    }
+}
+
+impl<T> Extend<T> for ArenaList<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for ele in iter {
+            self.push_back(ele);
+        }
+    }
+}
+
+impl<T> IntoIterator for ArenaList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArenaList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// iterator of elements from front to back
+pub struct Iter<'a, T> {
+    list: &'a ArenaList<T>,
+    front: Option<usize>,
+    back: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.front?;
+        self.len -= 1;
+        self.front = self.list.links(slot).1;
+        Some(self.list.value(slot))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.back?;
+        self.len -= 1;
+        self.back = self.list.links(slot).0;
+        Some(self.list.value(slot))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// iterator of mutable elements from front to back
+pub struct IterMut<'a, T> {
+    /// slots are visited at most once, so every element is borrowed once
+    entries: *mut Entry<T>,
+    front: Option<usize>,
+    back: Option<usize>,
+    len: usize,
+    _mark: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// return element and links of linked slot
+    unsafe fn entry(&self, slot: usize) -> (&'a mut T, Option<usize>, Option<usize>) {
+        match (*self.entries.add(slot)).slot {
+            Slot::Occupied { ref mut value, prev, next } => (value, prev, next),
+            Slot::Vacant { .. } => unreachable!("linked slot is vacant"),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.front?;
+        self.len -= 1;
+        let (value, _, next) = unsafe { self.entry(slot) };
+        self.front = next;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.back?;
+        self.len -= 1;
+        let (value, prev, _) = unsafe { self.entry(slot) };
+        self.back = prev;
+        Some(value)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// iterator which moves elements out of list
+pub struct IntoIter<T> {
+    list: ArenaList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// cursor for editing list in place, it has the methods of `linked::CursorMut` and
+/// can move back as the list is double linked
+/// ```
+/// use algorithms_rs::lists::arena::ArenaList;
+/// let mut ls: ArenaList<i32> = (0..5).collect();
+/// let mut cursor = ls.cursor_front_mut();
+/// cursor.move_next();
+/// assert_eq!(cursor.remove_current(), Some(1));
+/// cursor.insert_after(10);
+/// cursor.splice_after((20..22).collect());
+/// cursor.move_prev();
+/// assert_eq!(cursor.current(), Some(&mut 0));
+/// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 2, 20, 21, 10, 3, 4]);
+/// ```
+pub struct CursorMut<'a, T> {
+    list: &'a mut ArenaList<T>,
+    current: Option<usize>,
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// return index of current element, `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// return current element, `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut T> {
+        let slot = self.current?;
+        Some(self.list.value_mut(slot))
+    }
+
+    /// return handle of current element, `None` at the ghost position
+    pub fn handle(&self) -> Option<Index> {
+        self.current.map(|slot| self.list.handle(slot))
+    }
+
+    fn next_slot(&self) -> Option<usize> {
+        match self.current {
+            Some(slot) => self.list.links(slot).1,
+            None => self.list.head,
+        }
+    }
+
+    fn prev_slot(&self) -> Option<usize> {
+        match self.current {
+            Some(slot) => self.list.links(slot).0,
+            None => self.list.tail,
+        }
+    }
+
+    /// return the element after current one, the first element at the ghost position
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let slot = self.next_slot()?;
+        Some(self.list.value_mut(slot))
+    }
+
+    /// return the element before current one, the last element at the ghost position
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let slot = self.prev_slot()?;
+        Some(self.list.value_mut(slot))
+    }
+
+    /// move to the next element, the first element at the ghost position
+    pub fn move_next(&mut self) {
+        self.index = match self.current {
+            Some(_) => self.index + 1,
+            None => 0,
+        };
+        self.current = self.next_slot();
+    }
+
+    /// move to the previous element, the last element at the ghost position
+    pub fn move_prev(&mut self) {
+        // index of the ghost position is the length of list
+        self.current = self.prev_slot();
+        self.index = match self.current {
+            Some(_) => self.index - 1,
+            None => self.list.len,
+        };
+    }
+
+    /// insert element after current one, at the front at the ghost position
+    pub fn insert_after(&mut self, ele: T) -> Index {
+        let next = self.next_slot();
+        if self.current.is_none() {
+            self.index += 1;
+        }
+        self.list.insert_between(self.current, next, ele)
+    }
+
+    /// insert element before current one, at the back at the ghost position
+    pub fn insert_before(&mut self, ele: T) -> Index {
+        let prev = self.prev_slot();
+        self.index += 1;
+        self.list.insert_between(prev, self.current, ele)
+    }
+
+    /// remove current element and move to the next one, do nothing at the ghost position
+    pub fn remove_current(&mut self) -> Option<T> {
+        let slot = self.current?;
+        self.current = self.list.links(slot).1;
+        Some(self.list.remove_slot(slot))
+    }
+
+    /// move all elements of `other` after current one, at the front at the ghost position
+    /// 时间复杂度: O(m)
+    pub fn splice_after(&mut self, other: ArenaList<T>) {
+        for ele in other.into_iter().rev() {
+            self.insert_after(ele);
+        }
+    }
+}
+
+#[test]
+fn test_arena_list() {
+    let mut ls = ArenaList::new();
+    assert_eq!(ls.pop_front(), None);
+    assert_eq!(ls.pop_back(), None);
+    let one = ls.push_back(1);
+    assert_eq!(ls.pop(), Some(1));
+    assert_eq!(ls.get(one), None);
+    assert!(!ls.move_to_front(one));
+    // the slot is reused by a new generation
+    let two = ls.push_back(2);
+    assert_eq!(two.slot, one.slot);
+    assert_ne!(two, one);
+    assert_eq!(ls.remove(one), None);
+    let zero = ls.push_front(0);
+    ls.extend(3..6);
+    assert_eq!(format!("{:?}", ls), "[0, 2, 3, 4, 5]");
+    assert!(ls.move_to_back(zero));
+    assert!(ls.move_to_front(ls.back_index().unwrap()));
+    assert_eq!(ls.remove(ls.front_index().unwrap()), Some(0));
+    assert!(ls.move_to_back(two));
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 2]);
+    assert_eq!(ls.iter().rev().copied().collect::<Vec<_>>(), vec![2, 5, 4, 3]);
+    *ls.get_mut(two).unwrap() = 6;
+    *ls.peek_front_mut().unwrap() -= 1;
+    ls.iter_mut().rev().for_each(|x| *x *= 10);
+    assert_eq!(ls.peek_back(), Some(&60));
+    assert_eq!(ls.peek_front(), Some(&20));
+    assert!(ls.contains(&40));
+    assert!(ls.contains_index(two));
+
+    ls.reverse();
+    assert_eq!(ls.clone().into_iter().collect::<Vec<_>>(), vec![60, 50, 40, 20]);
+    assert_eq!(ls.remove_if(|x| *x > 45), 2);
+    assert!(!ls.contains_index(two));
+    let mut tail = ls.split_off(1).unwrap();
+    tail.push_back(10);
+    ls.append(&mut tail);
+    assert!(tail.is_empty());
+    assert_eq!(ls, vec![40, 20, 10].into_iter().collect());
+    assert_eq!(ls.len(), 3);
+    assert!(ls.capacity() >= 5);
+    ls.clear();
+    assert!(ls.is_empty());
+    assert!(ls.split_off(1).is_err());
+}
+
+#[test]
+fn test_arena_cursor() {
+    let mut ls: ArenaList<i32> = ArenaList::new();
+    let mut cursor = ls.cursor_front_mut();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(2);
+    cursor.insert_before(3);
+    cursor.splice_after((0..2).collect());
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut 0));
+    let zero = cursor.handle().unwrap();
+    cursor.move_prev();
+    assert_eq!(cursor.peek_prev(), Some(&mut 3));
+    assert_eq!(cursor.peek_next(), Some(&mut 0));
+    cursor.move_prev();
+    assert_eq!(cursor.index(), Some(3));
+    // remove the last element and the cursor moves to the ghost position
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(0));
+    assert_eq!(cursor.index(), Some(0));
+    cursor.insert_before(-1);
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(ls.get(zero), None);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![-1, 1, 2]);
+    assert_eq!(ls.peek_back(), Some(&2));
+    assert_eq!(ls.peek_front(), Some(&-1));
+}
+
+/// index of other list is rejected even if its slot and generation match
+#[test]
+fn test_arena_foreign_index() {
+    let mut ls: ArenaList<i32> = (0..3).collect();
+    let mut other: ArenaList<i32> = (10..13).collect();
+    let index = ls.front_index().unwrap();
+    let foreign = other.front_index().unwrap();
+    assert_eq!((index.slot, index.generation), (foreign.slot, foreign.generation));
+    assert_eq!(ls.get(foreign), None);
+    assert_eq!(ls.get_mut(foreign), None);
+    assert!(!ls.contains_index(foreign));
+    assert!(!ls.move_to_back(foreign));
+    assert_eq!(ls.remove(foreign), None);
+    assert_eq!(other.remove(index), None);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    // moved elements get indices of the new list
+    let mut tail = ls.split_off(1).unwrap();
+    let moved = tail.front_index().unwrap();
+    other.append(&mut tail);
+    assert_eq!(other.get(moved), None);
+    assert_eq!(other.remove(other.back_index().unwrap()), Some(2));
+    assert_eq!(ls.remove(index), Some(0));
+}
+
+/// no element is leaked or dropped twice
+#[test]
+fn test_arena_drop() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut ls: ArenaList<Rc<()>> = (0..10).map(|_| counter.clone()).collect();
+    let index = ls.front_index().unwrap();
+    ls.remove(index);
+    ls.remove_if({
+        let mut keep = false;
+        move |_| {
+            keep = !keep;
+            keep
+        }
+    });
+    let mut iter = ls.split_off(2).unwrap().into_iter();
+    iter.next_back();
+    drop(iter);
+    ls.push_back(counter.clone());
+    assert_eq!(Rc::strong_count(&counter), 4);
+    drop(ls);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
@@ -1,15 +1,18 @@
-use std::alloc::{alloc, dealloc, Layout, realloc, rust_oom};
-use std::fmt::Debug;
+use std::alloc::{handle_alloc_error, Layout};
+use std::fmt;
+use std::iter::FromIterator;
 use std::marker::PhantomData;
-use std::mem;
-use std::ops::{Deref, DerefMut, Index};
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds};
 use std::ptr;
+use std::slice::{self, SliceIndex};
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
 //
 use std::ptr::NonNull;
 
 use crate::error;
+use crate::lists::allocator::{Allocator, Global};
 
 const DEFAULT_CAP: usize = 5;
 
@@ -25,10 +28,10 @@ impl<T> RawPtr<T> {
             _mark: PhantomData,
         }
     }
-    #[allow(dead_code)]
+
     pub fn empty() -> Self {
         Self {
-            ptr: NonNull::new(mem::align_of::<T>() as *mut T).unwrap(),
+            ptr: NonNull::dangling(),
             _mark: PhantomData,
         }
     }
@@ -42,7 +45,7 @@ impl<T> RawPtr<T> {
     }
     /// return relative offset of pointer
     pub unsafe fn offset(&self, count: usize) -> *mut T {
-        self.ptr.as_ptr().offset(count as isize)
+        self.ptr.as_ptr().add(count)
     }
     /// read pointer of offset pointer to value
     pub unsafe fn read(&self, offset: usize) -> T {
@@ -66,37 +69,52 @@ unsafe impl<T: Send> Send for RawPtr<T> {}
 
 unsafe impl<T: Sync> Sync for RawPtr<T> {}
 
-pub struct Array<T> {
-    buf: RawArray<T>,
+/// growable array like `Vec`, the memory comes from allocator `A`
+/// elements of zero size are supported, they never allocate and the capacity is `usize::MAX`
+pub struct Array<T, A: Allocator = Global> {
+    buf: RawArray<T, A>,
     size: usize,
 }
 
 impl<T> Array<T> {
     /// create new Array with default capacity
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
     /// ```
     pub fn new() -> Self {
-        Self {
-            buf: RawArray::with_capacity(DEFAULT_CAP),
-            size: 0,
-        }
+        Self::with_capacity(DEFAULT_CAP)
     }
 
     /// create new Array with given capacity
-    /// ```no_run
+    /// ```
     ///
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::with_capacity(100);
     /// arr.append(5);
     /// ```
     pub fn with_capacity(cap: usize) -> Self {
-        Self {
-            buf: RawArray::with_capacity(cap),
-            size: 0,
-        }
+        Self::with_capacity_in(cap, Global)
+    }
+}
+
+impl<T, A: Allocator> Array<T, A> {
+    /// create new Array with default capacity in given allocator
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_capacity_in(DEFAULT_CAP, alloc)
+    }
+
+    /// create new Array with given capacity in given allocator
+    pub fn with_capacity_in(cap: usize, alloc: A) -> Self {
+        let mut buf = RawArray::new_in(alloc);
+        buf.resize(cap);
+        Self { buf, size: 0 }
+    }
+
+    /// return the allocator
+    pub fn allocator(&self) -> &A {
+        &self.buf.alloc
     }
 
     /// return true if size is 0
@@ -114,8 +132,18 @@ impl<T> Array<T> {
         self.buf.capacity
     }
 
+    /// return all elements as slice
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// return all elements as mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
     /// get element from array
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
@@ -129,56 +157,46 @@ impl<T> Array<T> {
     }
 
     /// get mutable element from array
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
-    /// let ele = raw.get_mut(0).unwrap();
+    /// let ele = arr.get_mut(0).unwrap();
     /// *ele = 10;
-    /// let ele = raw.get(0);
+    /// let ele = arr.get(0);
     /// assert_eq!(ele,Some(&10));
     /// ```
-    pub fn get_mut(&self, index: usize) -> Option<&mut T> {
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
         if index >= self.size {
             return None;
         }
         Some(unsafe { &mut *self.buf.ptr.offset(index) })
     }
 
-    /// insert element to array
-    /// # Zero Size Object
-    /// current version do not support zero size object, this is undefine behavior!!
-    ///
-    /// ```no_run
+    /// insert element to array, `index` can be the length to insert at the end
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
     /// arr.insert(0,10).unwrap();
-    /// assert_eq!(arr.get(0),&10);
+    /// assert_eq!(arr.get(0),Some(&10));
+    /// assert!(arr.insert(3, 1).is_err());
     /// ```
     pub fn insert(&mut self, index: usize, ele: T) -> Result<(), error::Error> {
-        assert_ne!(mem::size_of::<T>(), 0, "not support zero size object");
-        if index == 0 && self.size == 0 {
-            unsafe { self.buf.ptr.write(index, ele); }
-            self.size += 1;
-            return Ok(());
-        }
-
-        if index >= self.size {
+        if index > self.size {
             return Err(error::Error::IndexOfBounds);
         }
+        self.reserve(1);
         unsafe {
-            if index < self.size {
-                self.buf.ptr.copy(index, index + 1, self.size - index);
-            }
+            self.buf.ptr.copy(index, index + 1, self.size - index);
             self.buf.ptr.write(index, ele);
-            self.size += 1;
         }
+        self.size += 1;
         Ok(())
     }
 
     /// remove element from array
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
@@ -197,26 +215,60 @@ impl<T> Array<T> {
         }
     }
 
+    /// remove element from array and move the last element to its place
+    /// 时间复杂度: O(1)
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = vec![1, 2, 3, 4].into_iter().collect();
+    /// assert_eq!(arr.swap_remove(0).unwrap(), 1);
+    /// assert_eq!(arr, [4, 2, 3]);
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> Result<T, error::Error> {
+        if index >= self.size {
+            return Err(error::Error::IndexOfBounds);
+        }
+        self.size -= 1;
+        unsafe {
+            let ele = self.buf.ptr.read(index);
+            self.buf.ptr.copy(self.size, index, 1);
+            Ok(ele)
+        }
+    }
+
     /// append new element to array
-    /// # Zero Size Object
-    /// current version do not support zero size object, this is undefine behavior!!
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
     /// assert_eq!(arr.get(0),Some(&5));
     /// ```
     pub fn append(&mut self, ele: T) {
-        assert_ne!(mem::size_of::<T>(), 0, "not support zero size object");
-        if self.buf.capacity == self.size { unsafe { self.buf.alloc() } }
+        self.reserve(1);
         unsafe {
             self.buf.ptr.write(self.size, ele);
         }
         self.size += 1;
     }
 
+    /// append new element to array, return error instead of aborting if memory can't be allocated,
+    /// `ele` is dropped then
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr = Array::new();
+    /// arr.try_append(5).unwrap();
+    /// assert_eq!(arr.get(0),Some(&5));
+    /// ```
+    pub fn try_append(&mut self, ele: T) -> Result<(), error::Error> {
+        self.try_reserve(1)?;
+        unsafe {
+            self.buf.ptr.write(self.size, ele);
+        }
+        self.size += 1;
+        Ok(())
+    }
+
     /// pop the last element from array
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr = Array::new();
     /// arr.append(5);
@@ -225,22 +277,199 @@ impl<T> Array<T> {
     /// assert_eq!(arr.get(0),None);
     /// ```
     pub fn pop(&mut self) -> Option<T> {
-        return if self.size == 0 {
+        if self.size == 0 {
             None
         } else {
             self.size -= 1;
             unsafe {
                 Some(self.buf.ptr.read(self.size))
             }
+        }
+    }
+
+    /// make sure there is room for at least `additional` more elements,
+    /// the capacity is at least doubled when it grows
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = Array::with_capacity(0);
+    /// arr.reserve(10);
+    /// assert!(arr.cap() >= 10);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(e) = self.try_reserve(additional) {
+            fail(e)
+        }
+    }
+
+    /// make sure there is room for at least `additional` more elements,
+    /// return error instead of aborting if the capacity overflows or memory can't be allocated
+    /// ```
+    /// use algorithms_rs::error::Error;
+    /// use algorithms_rs::lists::array::Array;
+    ///
+    /// let mut arr: Array<u64> = Array::new();
+    /// assert!(arr.try_reserve(10).is_ok());
+    /// match arr.try_reserve(usize::MAX) {
+    ///     Err(Error::CapacityOverflow) => {}
+    ///     _ => panic!("must overflow"),
+    /// }
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), error::Error> {
+        let required = self.size.checked_add(additional).ok_or(error::Error::CapacityOverflow)?;
+        if required > self.buf.capacity {
+            self.buf.try_resize(required.max(self.buf.capacity.saturating_mul(2)))?;
+        }
+        Ok(())
+    }
+
+    /// shrink the capacity to the length
+    pub fn shrink_to_fit(&mut self) {
+        if self.buf.capacity > self.size {
+            self.buf.resize(self.size);
+        }
+    }
+
+    /// keep the first `len` elements and drop the rest, nothing happens if `len` is not less than length
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = (0..5).collect();
+    /// arr.truncate(2);
+    /// assert_eq!(arr, [0, 1]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.size {
+            let tail = ptr::slice_from_raw_parts_mut(unsafe { self.buf.ptr.offset(len) }, self.size - len);
+            // shorten first, so a panicking drop doesn't leave dropped elements in array
+            self.size = len;
+            unsafe { ptr::drop_in_place(tail) }
+        }
+    }
+
+    /// drop all elements, the capacity is kept
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// split array into two at `at`, return elements after `at` and keep `[0, at)`
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = (0..5).collect();
+    /// let tail = arr.split_off(3).unwrap();
+    /// assert_eq!(arr, [0, 1, 2]);
+    /// assert_eq!(tail, [3, 4]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Result<Array<T, A>, error::Error>
+        where A: Clone
+    {
+        if at > self.size {
+            return Err(error::Error::IndexOfBounds);
+        }
+        let count = self.size - at;
+        let mut other = Array::with_capacity_in(count, self.buf.alloc.clone());
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.ptr.offset(at), other.buf.ptr.as_mut(), count);
+        }
+        self.size = at;
+        other.size = count;
+        Ok(other)
+    }
+
+    /// remove elements in `range` and return them as iterator,
+    /// the elements not iterated are dropped when the iterator is dropped
+    /// # Panics
+    /// panics if the range is out of bounds or its start is greater than end
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = (0..6).collect();
+    /// let drained: Vec<i32> = arr.drain(1..4).collect();
+    /// assert_eq!(drained, vec![1, 2, 3]);
+    /// assert_eq!(arr, [0, 4, 5]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A>
+        where R: RangeBounds<usize>
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
         };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.size,
+        };
+        assert!(start <= end, "drain range starts at {} but ends at {}", start, end);
+        assert!(end <= self.size, "drain range end {} is out of bounds of length {}", end, self.size);
+        let tail_len = self.size - end;
+        // elements from `start` are out of array until the drain is dropped
+        self.size = start;
+        Drain {
+            arr: self,
+            front: start,
+            back: end,
+            tail_start: end,
+            tail_len,
+        }
+    }
+
+    /// keep only elements which `keep` returns true for, in their original order
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = (0..10).collect();
+    /// arr.retain(|x| x % 3 == 0);
+    /// assert_eq!(arr, [0, 3, 6, 9]);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+        where F: FnMut(&T) -> bool
+    {
+        let mut compact = Compact::new(self);
+        while compact.read < compact.len {
+            let current = unsafe { &*compact.arr.buf.ptr.offset(compact.read) };
+            if keep(current) {
+                compact.keep();
+            } else {
+                compact.discard();
+            }
+        }
+    }
+
+    /// remove consecutive elements which `same_bucket(current, previous)` returns true for,
+    /// `previous` is the last element kept
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+        where F: FnMut(&mut T, &mut T) -> bool
+    {
+        if self.size <= 1 {
+            return;
+        }
+        let mut compact = Compact::new(self);
+        compact.keep();
+        while compact.read < compact.len {
+            let (current, previous) = unsafe {
+                let ptr = &compact.arr.buf.ptr;
+                (&mut *ptr.offset(compact.read), &mut *ptr.offset(compact.write - 1))
+            };
+            if same_bucket(current, previous) {
+                compact.discard();
+            } else {
+                compact.keep();
+            }
+        }
+    }
+
+    /// remove consecutive elements with the same key
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+        where K: PartialEq,
+              F: FnMut(&mut T) -> K
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
     }
 }
 
-impl<T> Array<T>
+impl<T, A: Allocator> Array<T, A>
     where T: Eq + PartialEq
 {
     /// return index of array with given element if not found return None
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr =  Array::new();
     /// arr.append(5);
@@ -249,17 +478,10 @@ impl<T> Array<T>
     /// assert_eq!(arr.find(&5),Some(0));
     /// ```
     pub fn find(&self, ele: &T) -> Option<usize> {
-        for i in 0..self.size {
-            if let Some(data) = self.get(i) {
-                if ele == data {
-                    return Some(i);
-                }
-            }
-        }
-        None
+        self.iter().position(|data| data == ele)
     }
     /// return true if contains given element otherwise return false
-    /// ```no_run
+    /// ```
     /// use algorithms_rs::lists::array::Array;
     /// let mut arr =  Array::new();
     /// arr.append(5);
@@ -268,91 +490,351 @@ impl<T> Array<T>
     /// assert!(arr.contains(&5));
     /// ```
     pub fn contains(&self, ele: &T) -> bool {
-        for i in 0..self.size {
-            if Some(ele) == self.get(i) {
-                return true;
+        self.find(ele).is_some()
+    }
+
+    /// remove consecutive equal elements, the array has no duplicate if it's sorted
+    /// ```
+    /// use algorithms_rs::lists::array::Array;
+    /// let mut arr: Array<i32> = vec![1, 1, 2, 3, 3, 3, 1].into_iter().collect();
+    /// arr.dedup();
+    /// assert_eq!(arr, [1, 2, 3, 1]);
+    /// ```
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+impl<T> Default for Array<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// panic on error of allocation, or abort if memory can't be allocated like `Vec`
+fn fail(e: error::Error) -> ! {
+    match e {
+        error::Error::AllocError(layout) => handle_alloc_error(layout),
+        e => panic!("{}", e),
+    }
+}
+
+impl<T, A: Allocator, I: SliceIndex<[T]>> Index<I> for Array<T, A> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(&**self, index)
+    }
+}
+
+impl<T, A: Allocator, I: SliceIndex<[T]>> IndexMut<I> for Array<T, A> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(&mut **self, index)
+    }
+}
+
+impl<T, A: Allocator> Drop for Array<T, A> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(&mut **self) }
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for Array<T, A> {
+    fn clone(&self) -> Self {
+        let mut other = Array::with_capacity_in(self.size, self.buf.alloc.clone());
+        other.extend(self.iter().cloned());
+        other
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for Array<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, U, A, B> PartialEq<Array<U, B>> for Array<T, A>
+    where T: PartialEq<U>,
+          A: Allocator,
+          B: Allocator
+{
+    fn eq(&self, other: &Array<U, B>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<[U]> for Array<T, A> {
+    fn eq(&self, other: &[U]) -> bool {
+        self[..] == *other
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<[U; N]> for Array<T, A> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U, A: Allocator> PartialEq<Vec<U>> for Array<T, A> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for Array<T, A> {}
+
+impl<T> FromIterator<T> for Array<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut arr = Array::with_capacity(iter.size_hint().0);
+        arr.extend(iter);
+        arr
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for Array<T, A> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for ele in iter {
+            self.append(ele);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator> Extend<&'a T> for Array<T, A> {
+    fn extend<I: IntoIterator<Item=&'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for Array<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> IntoIter<T, A> {
+        let arr = ManuallyDrop::new(self);
+        IntoIter {
+            // the buffer is moved out and freed by the iterator
+            buf: unsafe { ptr::read(&arr.buf) },
+            front: 0,
+            back: arr.size,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a Array<T, A> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: Allocator> IntoIterator for &'a mut Array<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// owned iterator of `Array`
+pub struct IntoIter<T, A: Allocator = Global> {
+    buf: RawArray<T, A>,
+    front: usize,
+    back: usize,
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(unsafe { self.buf.ptr.read(self.front - 1) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(unsafe { self.buf.ptr.read(self.back) })
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        for _ in self {}
+    }
+}
+
+/// draining iterator of `Array`, see `Array::drain`
+pub struct Drain<'a, T, A: Allocator = Global> {
+    arr: &'a mut Array<T, A>,
+    front: usize,
+    back: usize,
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<'a, T, A: Allocator> Iterator for Drain<'a, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(unsafe { self.arr.buf.ptr.read(self.front - 1) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, A: Allocator> DoubleEndedIterator for Drain<'a, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(unsafe { self.arr.buf.ptr.read(self.back) })
+    }
+}
+
+impl<'a, T, A: Allocator> ExactSizeIterator for Drain<'a, T, A> {}
+
+impl<'a, T, A: Allocator> Drop for Drain<'a, T, A> {
+    fn drop(&mut self) {
+        // move the tail back even if dropping the rest panics
+        struct MoveTail<'r, 'a, T, A: Allocator>(&'r mut Drain<'a, T, A>);
+
+        impl<'r, 'a, T, A: Allocator> Drop for MoveTail<'r, 'a, T, A> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.arr.size;
+                unsafe { drain.arr.buf.ptr.copy(drain.tail_start, start, drain.tail_len) }
+                drain.arr.size = start + drain.tail_len;
             }
         }
-        false
+
+        let guard = MoveTail(self);
+        for _ in &mut *guard.0 {}
     }
 }
 
-impl<T> Index<usize> for Array<T> {
-    type Output = T;
+/// compact array in place by moving kept elements forward,
+/// elements before `read` are processed and `write` of them are kept,
+/// if it's dropped in the middle by panic, the unprocessed elements are moved after kept ones
+struct Compact<'a, T, A: Allocator> {
+    arr: &'a mut Array<T, A>,
+    read: usize,
+    write: usize,
+    len: usize,
+}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).unwrap()
+impl<'a, T, A: Allocator> Compact<'a, T, A> {
+    fn new(arr: &'a mut Array<T, A>) -> Self {
+        let len = arr.size;
+        // the array is empty while compacting, so nothing is dropped twice on panic
+        arr.size = 0;
+        Self { arr, read: 0, write: 0, len }
+    }
+
+    /// keep `arr[read]`
+    fn keep(&mut self) {
+        if self.read != self.write {
+            unsafe { self.arr.buf.ptr.copy(self.read, self.write, 1) }
+        }
+        self.read += 1;
+        self.write += 1;
+    }
+
+    /// drop `arr[read]`
+    fn discard(&mut self) {
+        self.read += 1;
+        unsafe { ptr::drop_in_place(self.arr.buf.ptr.offset(self.read - 1)) }
     }
 }
 
-impl<T> Drop for Array<T> {
+impl<'a, T, A: Allocator> Drop for Compact<'a, T, A> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        let rest = self.len - self.read;
+        unsafe { self.arr.buf.ptr.copy(self.read, self.write, rest) }
+        self.arr.size = self.write + rest;
     }
 }
 
-struct RawArray<T> {
+struct RawArray<T, A: Allocator> {
     ptr: RawPtr<T>,
     capacity: usize,
+    alloc: A,
 }
 
-impl<T> RawArray<T> {
-    #[allow(dead_code)]
-    fn new() -> Self {
-        assert_ne!(mem::size_of::<T>(), 0, "not ready");
+impl<T, A: Allocator> RawArray<T, A> {
+    /// create empty buffer, zero size elements never need memory so the capacity is unlimited
+    fn new_in(alloc: A) -> Self {
         Self {
             ptr: RawPtr::empty(),
-            capacity: 0,
+            capacity: if mem::size_of::<T>() == 0 { usize::MAX } else { 0 },
+            alloc,
         }
     }
 
-    fn with_capacity(cap: usize) -> Self {
-        let align = mem::align_of::<T>();
-        let size = mem::size_of::<T>() * cap;
-        let (ptr, layout) = unsafe {
-            let layout = Layout::from_size_align_unchecked(size, align);
-            let ptr = alloc(layout);
-            (ptr, layout)
-        };
-        if ptr.is_null() {
-            rust_oom(layout);
-        }
-        Self {
-            capacity: cap,
-            ptr: RawPtr::new(ptr as *mut T),
+    /// reallocate buffer to hold `cap` elements, abort if memory can't be allocated
+    fn resize(&mut self, cap: usize) {
+        if let Err(e) = self.try_resize(cap) {
+            fail(e)
         }
     }
 
-    unsafe fn alloc(&mut self) {
-        let align = mem::align_of::<T>();
-        let size = mem::size_of::<T>();
-        assert_ne!(size, 0, "capacity overflow");
-        let (new_cap, ptr, layout) = if self.capacity == 0 {
-            let layout = Layout::from_size_align_unchecked(size, align);
-            let ptr = alloc(layout);
-            (1, ptr, layout)
-        } else {
-            let cap = self.capacity * 2;
-            let old_size = self.capacity * size;
-            assert!(old_size <= (isize::max_value() as usize) / 2);
-            let n_size = old_size * 2;
-            let layout = Layout::from_size_align_unchecked(old_size, align);
-            let ptr = realloc(
-                self.ptr.as_mut() as *mut u8,
-                layout,
-                n_size,
-            );
-            (cap, ptr, layout)
-        };
-        if ptr.is_null() {
-            rust_oom(layout);
+    /// reallocate buffer to hold `cap` elements, the elements must fit in the new capacity,
+    /// the buffer is kept if it's failed
+    fn try_resize(&mut self, cap: usize) -> Result<(), error::Error> {
+        if mem::size_of::<T>() == 0 || cap == self.capacity {
+            return Ok(());
         }
-        self.ptr = RawPtr::new(ptr as *mut T);
-        self.capacity = new_cap;
+        let layout = Layout::array::<T>(cap).map_err(|_| error::Error::CapacityOverflow)?;
+        let ptr = unsafe {
+            let old_ptr = NonNull::new_unchecked(self.ptr.as_mut() as *mut u8);
+            // the old layout was checked when it was allocated
+            let old_layout = Layout::array::<T>(self.capacity).unwrap();
+            if self.capacity == 0 {
+                self.alloc.allocate(layout)
+            } else if cap == 0 {
+                self.alloc.deallocate(old_ptr, old_layout);
+                self.ptr = RawPtr::empty();
+                self.capacity = 0;
+                return Ok(());
+            } else {
+                self.alloc.reallocate(old_ptr, old_layout, layout.size())
+            }
+        };
+        let ptr = ptr.ok_or(error::Error::AllocError(layout))?;
+        self.ptr = RawPtr::new(ptr.as_ptr() as *mut T);
+        self.capacity = cap;
+        Ok(())
     }
 }
 
-impl<T> Deref for Array<T> {
+impl<T, A: Allocator> Deref for Array<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
@@ -362,7 +844,7 @@ impl<T> Deref for Array<T> {
     }
 }
 
-impl<T> DerefMut for Array<T> {
+impl<T, A: Allocator> DerefMut for Array<T, A> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         unsafe {
             std::slice::from_raw_parts_mut(self.buf.ptr.as_ptr(), self.size)
@@ -370,19 +852,9 @@ impl<T> DerefMut for Array<T> {
     }
 }
 
-impl<T> Drop for RawArray<T> {
+impl<T, A: Allocator> Drop for RawArray<T, A> {
     fn drop(&mut self) {
-        if self.capacity != 0 {
-            let align = mem::align_of::<T>();
-            let size = mem::size_of::<T>();
-            let nums = size * self.capacity;
-            unsafe {
-                dealloc(
-                    self.ptr.as_mut() as *mut u8,
-                    Layout::from_size_align_unchecked(nums, align),
-                )
-            }
-        }
+        self.resize(0)
     }
 }
 
@@ -432,10 +904,201 @@ fn test() -> Result<(), error::Error> {
     Ok(())
 }
 
-fn print_arr<T: Debug>(arr: &Array<T>) {
+#[cfg(test)]
+fn print_arr<T: fmt::Debug>(arr: &Array<T>) {
     for i in 0..arr.len() {
         if let Some(data) = arr.get(i) {
             println!("{:?}", data);
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_vec_api() {
+    use std::rc::Rc;
+
+    // insert grows the capacity, and may insert at the end
+    let mut arr = Array::with_capacity(0);
+    for i in 0..20 {
+        arr.insert(i / 2, i).unwrap();
+    }
+    assert_eq!(arr.len(), 20);
+    assert!(arr.insert(21, 0).is_err());
+
+    let mut arr: Array<i32> = (0..10).collect();
+    arr.extend(vec![10, 11]);
+    arr.extend(&[12, 13]);
+    assert_eq!(arr, (0..14).collect::<Vec<_>>());
+    assert_eq!(format!("{:?}", arr), format!("{:?}", (0..14).collect::<Vec<_>>()));
+    assert_eq!(&arr[2..4], &[2, 3]);
+    arr[0] = 100;
+    for x in &mut arr {
+        *x += 1;
+    }
+    assert_eq!((&arr).into_iter().sum::<i32>(), (1..15).sum::<i32>() + 100);
+
+    let copy = arr.clone();
+    assert_eq!(copy, arr);
+    assert_eq!(copy.into_iter().rev().take(2).collect::<Vec<_>>(), vec![14, 13]);
+
+    assert_eq!(arr.swap_remove(0).unwrap(), 101);
+    assert_eq!(arr[0], 14);
+    assert!(arr.swap_remove(100).is_err());
+    let tail = arr.split_off(10).unwrap();
+    assert_eq!(tail, [11, 12, 13]);
+    assert!(arr.split_off(11).is_err());
+    assert_eq!(arr.split_off(10).unwrap(), []);
+
+    arr.retain(|x| x % 2 == 0);
+    assert_eq!(arr, [14, 2, 4, 6, 8, 10]);
+    let mut drain = arr.drain(1..=3);
+    assert_eq!(drain.next_back(), Some(6));
+    assert_eq!(drain.len(), 2);
+    drop(drain);
+    assert_eq!(arr, [14, 8, 10]);
+    assert_eq!(arr.drain(..).collect::<Vec<_>>(), vec![14, 8, 10]);
+    assert!(arr.is_empty());
+
+    arr.extend(vec![1, 1, 2, 2, 2, 3, 1, 1]);
+    arr.dedup();
+    assert_eq!(arr, [1, 2, 3, 1]);
+    arr.dedup_by_key(|x| *x / 2);
+    assert_eq!(arr, [1, 2, 1]);
+
+    arr.truncate(1);
+    assert_eq!(arr, [1]);
+    arr.shrink_to_fit();
+    assert_eq!(arr.cap(), 1);
+    arr.clear();
+    arr.shrink_to_fit();
+    assert_eq!(arr.cap(), 0);
+    arr.reserve(3);
+    assert!(arr.cap() >= 3);
+
+    // every element is dropped exactly once
+    let counter = Rc::new(());
+    let mut arr: Array<Rc<()>> = (0..10).map(|_| counter.clone()).collect();
+    arr.truncate(8);
+    arr.retain({
+        let mut keep = false;
+        move |_| {
+            keep = !keep;
+            keep
+        }
+    });
+    arr.drain(1..3).next();
+    arr.dedup_by(|_, _| false);
+    let mut iter = arr.clone().into_iter();
+    iter.next();
+    drop(iter);
+    assert_eq!(Rc::strong_count(&counter), 3);
+    drop(arr);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+/// elements stay valid when the closure of `retain` panics
+#[test]
+fn test_retain_panic() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut arr: Array<Rc<()>> = (0..6).map(|_| counter.clone()).collect();
+    let mut calls = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        arr.retain(|_| {
+            calls += 1;
+            assert!(calls < 4);
+            calls % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+    // 2 of the 3 processed elements are dropped
+    assert_eq!(arr.len(), 4);
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(arr);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn test_zero_size() {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Empty;
+
+    let mut arr = Array::new();
+    assert_eq!(arr.cap(), usize::MAX);
+    for _ in 0..10 {
+        arr.append(Empty);
+    }
+    arr.insert(5, Empty).unwrap();
+    assert_eq!(arr.remove(0).unwrap(), Empty);
+    assert_eq!(arr.len(), 10);
+    assert_eq!(arr.drain(2..4).count(), 2);
+    assert_eq!(arr.clone().into_iter().rev().count(), 8);
+    arr.dedup();
+    assert_eq!(arr, [Empty]);
+    assert!(arr.try_reserve(usize::MAX).is_err());
+
+    // drop is still called for every element
+    static DROPS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    struct Counter;
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+    let mut arr = Array::new();
+    for _ in 0..5 {
+        arr.append(Counter);
+    }
+    arr.truncate(3);
+    assert_eq!(DROPS.load(std::sync::atomic::Ordering::SeqCst), 2);
+    drop(arr);
+    assert_eq!(DROPS.load(std::sync::atomic::Ordering::SeqCst), 5);
+}
+
+/// allocator which fails after `remain` allocations
+#[cfg(test)]
+struct Limited {
+    remain: std::cell::Cell<usize>,
+}
+
+#[cfg(test)]
+unsafe impl Allocator for &Limited {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        if self.remain.get() == 0 {
+            return None;
+        }
+        self.remain.set(self.remain.get() - 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn test_allocator() {
+    let limited = Limited { remain: std::cell::Cell::new(2) };
+    let mut arr = Array::with_capacity_in(2, &limited);
+    arr.try_append(1).unwrap();
+    arr.try_append(2).unwrap();
+    // the second allocation grows the buffer, the third one fails
+    arr.try_append(3).unwrap();
+    assert_eq!(arr.cap(), 4);
+    arr.try_reserve(1).unwrap();
+    match arr.try_reserve(10) {
+        Err(error::Error::AllocError(layout)) => assert_eq!(layout.size(), 13 * mem::size_of::<i32>()),
+        _ => panic!("allocation must fail"),
+    }
+    assert!(arr.try_append(4).is_ok());
+    // the buffer is kept after failure
+    assert_eq!(arr, [1, 2, 3, 4]);
+    assert_eq!(arr.cap(), 4);
+    match arr.try_reserve(usize::MAX) {
+        Err(error::Error::CapacityOverflow) => {}
+        _ => panic!("capacity must overflow"),
+    }
+    assert_eq!(format!("{}", error::Error::CapacityOverflow), "Capacity overflow!");
+}
@@ -1,3 +0,0 @@
-// Project algorithms-rs
-// Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
@@ -0,0 +1,898 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/7/13 17:53
+//
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error;
+use crate::lists::double_linked::raw::RawStorage;
+
+pub mod raw;
+pub mod rc;
+
+/// double linked list with nodes shared by `Rc`, easier to follow but slower
+pub type RcDoubleLinkedList<T> = DoubleLinkedList<T, rc::RcStorage<T>>;
+
+/// return unique id for new list, so elements and indices can tell which list they belong to
+pub(crate) fn next_id() -> usize {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// nodes and links of `DoubleLinkedList`, every operation takes O(1)
+///
+/// `Node` is position of node used while the list is borrowed, it's cheap but can't be checked,
+/// so the methods taking it are unsafe. `Handle` is kept by user, it stays valid until the node
+/// is removed, moving node to other storage by `splice_after` or `split_after` moves its handle
+/// too. Which storage holds the node isn't recorded, that would make moving nodes O(k),
+/// so `resolve` can only tell removed nodes and is unsafe as well
+/// # Safety
+/// nodes must be linked in order of insertion, `resolve` must return `None` for removed nodes
+pub unsafe trait Storage<T>: Default {
+    /// position of node
+    type Node: Clone;
+    /// stable handle of node
+    type Handle: Clone;
+    /// reference of element
+    type Ref<'a>: Deref<Target=T> where Self: 'a, T: 'a;
+
+    /// return number of elements
+    fn len(&self) -> usize;
+
+    /// return true if there is no element
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// return the first node
+    fn head(&self) -> Option<Self::Node>;
+
+    /// return the last node
+    fn tail(&self) -> Option<Self::Node>;
+
+    /// return node after `node`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn next(&self, node: &Self::Node) -> Option<Self::Node>;
+
+    /// return node before `node`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn prev(&self, node: &Self::Node) -> Option<Self::Node>;
+
+    /// return element of `node`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn get<'a>(&'a self, node: &Self::Node) -> Self::Ref<'a>;
+
+    /// insert element after `node`, or at the front if `node` is `None`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn insert_after(&mut self, node: Option<&Self::Node>, ele: T) -> Self::Node;
+
+    /// insert element before `node`, or at the back if `node` is `None`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn insert_before(&mut self, node: Option<&Self::Node>, ele: T) -> Self::Node;
+
+    /// unlink `node` and return its element
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn remove(&mut self, node: Self::Node) -> T;
+
+    /// move all nodes of `other` after `node`, or at the front if `node` is `None`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn splice_after(&mut self, node: Option<&Self::Node>, other: Self);
+
+    /// move the `count` nodes after `node` to new storage, or all nodes if `node` is `None`
+    /// # Safety
+    /// `node` must be in this storage and `count` must be the number of nodes after it
+    unsafe fn split_after(&mut self, node: Option<&Self::Node>, count: usize) -> Self;
+
+    /// return handle of `node`
+    /// # Safety
+    /// `node` must be in this storage
+    unsafe fn handle(&self, node: &Self::Node) -> Self::Handle;
+
+    /// return node of `handle`, `None` if the node is removed,
+    /// implementations should check the node is in this storage in debug builds
+    /// # Safety
+    /// the node of `handle` must be in this storage unless it's removed
+    unsafe fn resolve(&self, handle: &Self::Handle) -> Option<Self::Node>;
+}
+
+/// double linked list, push and pop at both ends, splice and split at cursor take O(1)
+///
+/// nodes are stored by `S`: `raw::RawStorage` (the default) uses raw pointers for speed,
+/// `rc::RcStorage` uses `Rc` and `Weak` and needs no unsafe code to link nodes
+/// ```
+/// use algorithms_rs::lists::double_linked::{DoubleLinkedList, RcDoubleLinkedList};
+///
+/// let mut ls: DoubleLinkedList<i32> = DoubleLinkedList::new();
+/// ls.push_back(2);
+/// ls.push_front(1);
+/// ls.push_back(3);
+/// assert_eq!(ls.iter().rev().map(|x| *x).collect::<Vec<_>>(), vec![3, 2, 1]);
+///
+/// // the same API backed by Rc
+/// let mut ls: RcDoubleLinkedList<i32> = (1..4).collect();
+/// assert_eq!(ls.pop_back(), Some(3));
+/// assert_eq!(*ls.front().unwrap(), 1);
+/// ```
+pub struct DoubleLinkedList<T, S: Storage<T> = RawStorage<T>> {
+    storage: S,
+    _mark: PhantomData<T>,
+}
+
+impl<T, S: Storage<T>> DoubleLinkedList<T, S> {
+    pub fn new() -> Self {
+        Self {
+            storage: S::default(),
+            _mark: PhantomData,
+        }
+    }
+
+    fn from_storage(storage: S) -> Self {
+        Self {
+            storage,
+            _mark: PhantomData,
+        }
+    }
+
+    /// return number of elements
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// drop all elements
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn push_front(&mut self, ele: T) {
+        unsafe {
+            self.storage.insert_after(None, ele);
+        }
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn push_back(&mut self, ele: T) {
+        unsafe {
+            self.storage.insert_before(None, ele);
+        }
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.storage.head()?;
+        Some(unsafe { self.storage.remove(node) })
+    }
+
+    /// 时间复杂度: O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        let node = self.storage.tail()?;
+        Some(unsafe { self.storage.remove(node) })
+    }
+
+    /// return the first element
+    pub fn front(&self) -> Option<S::Ref<'_>> {
+        let node = self.storage.head()?;
+        Some(unsafe { self.storage.get(&node) })
+    }
+
+    /// return the last element
+    pub fn back(&self) -> Option<S::Ref<'_>> {
+        let node = self.storage.tail()?;
+        Some(unsafe { self.storage.get(&node) })
+    }
+
+    /// return handle of the first element
+    pub fn front_handle(&self) -> Option<S::Handle> {
+        let node = self.storage.head()?;
+        Some(unsafe { self.storage.handle(&node) })
+    }
+
+    /// return handle of the last element
+    pub fn back_handle(&self) -> Option<S::Handle> {
+        let node = self.storage.tail()?;
+        Some(unsafe { self.storage.handle(&node) })
+    }
+
+    /// return element of `handle`, `None` if it's removed
+    /// 时间复杂度: O(1), O(n) in debug builds to check the handle
+    ///
+    /// # Safety
+    ///
+    /// the element of `handle` must be in this list unless it's removed, a handle moves with
+    /// its element by `splice_after`, `split_after`, `append` and `split_off`,
+    /// so after that it must be used with the list the element is moved to
+    pub unsafe fn get(&self, handle: &S::Handle) -> Option<S::Ref<'_>> {
+        let node = self.storage.resolve(handle)?;
+        Some(self.storage.get(&node))
+    }
+
+    /// remove element of `handle`, `None` if it's removed
+    /// 时间复杂度: O(1), O(n) in debug builds to check the handle
+    ///
+    /// # Safety
+    ///
+    /// same as [`get`](DoubleLinkedList::get)
+    /// ```
+    /// use algorithms_rs::lists::double_linked::DoubleLinkedList;
+    ///
+    /// let mut ls: DoubleLinkedList<i32> = (0..3).collect();
+    /// let mut cursor = ls.cursor_front_mut();
+    /// cursor.move_next();
+    /// let handle = cursor.handle().unwrap();
+    /// ls.push_front(-1);
+    /// unsafe {
+    ///     assert_eq!(ls.remove(&handle), Some(1));
+    ///     assert_eq!(ls.remove(&handle), None);
+    /// }
+    /// assert_eq!(ls.iter().map(|x| *x).collect::<Vec<_>>(), vec![-1, 0, 2]);
+    /// ```
+    pub unsafe fn remove(&mut self, handle: &S::Handle) -> Option<T> {
+        let node = self.storage.resolve(handle)?;
+        Some(self.storage.remove(node))
+    }
+
+    /// return true if element of `handle` isn't removed
+    ///
+    /// # Safety
+    ///
+    /// same as [`get`](DoubleLinkedList::get)
+    pub unsafe fn contains_handle(&self, handle: &S::Handle) -> bool {
+        self.storage.resolve(handle).is_some()
+    }
+
+    /// return true if list contains `ele`
+    pub fn contains(&self, ele: &T) -> bool
+        where T: PartialEq
+    {
+        self.iter().any(|x| *x == *ele)
+    }
+
+    /// iterate elements from front to back
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter {
+            storage: &self.storage,
+            front: self.storage.head(),
+            back: self.storage.tail(),
+            len: self.len(),
+        }
+    }
+
+    /// move all elements of `other` to the back, `other` becomes empty
+    /// 时间复杂度: O(1)
+    pub fn append(&mut self, other: &mut Self) {
+        let other = mem::take(&mut other.storage);
+        let tail = self.storage.tail();
+        unsafe { self.storage.splice_after(tail.as_ref(), other) }
+    }
+
+    /// split list into two at `at`, return elements after `at` and keep `[0, at)`
+    /// 时间复杂度: O(min(at, n - at))
+    /// ```
+    /// use algorithms_rs::lists::double_linked::DoubleLinkedList;
+    ///
+    /// let mut ls: DoubleLinkedList<i32> = (0..5).collect();
+    /// let tail = ls.split_off(3).unwrap();
+    /// assert_eq!(ls.iter().map(|x| *x).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(tail.iter().map(|x| *x).collect::<Vec<_>>(), vec![3, 4]);
+    /// assert!(ls.split_off(4).is_err());
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Result<Self, error::Error> {
+        let len = self.len();
+        if at > len {
+            return Err(error::Error::IndexOfBounds);
+        }
+        // find the node before `at` from the nearer end
+        let node = unsafe {
+            if at == 0 {
+                None
+            } else if at <= len - at {
+                let mut node = self.storage.head();
+                for _ in 1..at {
+                    node = self.storage.next(node.as_ref().unwrap());
+                }
+                node
+            } else {
+                let mut node = self.storage.tail();
+                for _ in at..len {
+                    node = self.storage.prev(node.as_ref().unwrap());
+                }
+                node
+            }
+        };
+        let other = unsafe { self.storage.split_after(node.as_ref(), len - at) };
+        Ok(Self::from_storage(other))
+    }
+
+    /// return cursor at the first element, or at the "ghost" position if list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, S> {
+        CursorMut {
+            current: self.storage.head(),
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// return cursor at the last element, or at the "ghost" position if list is empty
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, S> {
+        CursorMut {
+            current: self.storage.tail(),
+            index: self.len().saturating_sub(1),
+            list: self,
+        }
+    }
+}
+
+impl<T, S: Storage<T>> Default for DoubleLinkedList<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, S: Storage<T>> Clone for DoubleLinkedList<T, S> {
+    fn clone(&self) -> Self {
+        self.iter().map(|x| x.clone()).collect()
+    }
+}
+
+impl<T: fmt::Debug, S: Storage<T>> fmt::Debug for DoubleLinkedList<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for ele in self.iter() {
+            list.entry(&*ele);
+        }
+        list.finish()
+    }
+}
+
+impl<T: PartialEq, S: Storage<T>> PartialEq for DoubleLinkedList<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| *a == *b)
+    }
+}
+
+impl<T: Eq, S: Storage<T>> Eq for DoubleLinkedList<T, S> {}
+
+impl<T, S: Storage<T>> FromIterator<T> for DoubleLinkedList<T, S> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut ls = Self::new();
+        ls.extend(iter);
+        ls
+    }
+}
+
+impl<T, S: Storage<T>> Extend<T> for DoubleLinkedList<T, S> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for ele in iter {
+            self.push_back(ele);
+        }
+    }
+}
+
+impl<T, S: Storage<T>> IntoIterator for DoubleLinkedList<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T, S>;
+
+    fn into_iter(self) -> IntoIter<T, S> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T: 'a, S: Storage<T>> IntoIterator for &'a DoubleLinkedList<T, S> {
+    type Item = S::Ref<'a>;
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Iter<'a, T, S> {
+        self.iter()
+    }
+}
+
+/// iterator of elements from front to back
+pub struct Iter<'a, T, S: Storage<T>> {
+    storage: &'a S,
+    front: Option<S::Node>,
+    back: Option<S::Node>,
+    len: usize,
+}
+
+impl<'a, T: 'a, S: Storage<T>> Iterator for Iter<'a, T, S> {
+    type Item = S::Ref<'a>;
+
+    fn next(&mut self) -> Option<S::Ref<'a>> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        self.len -= 1;
+        unsafe {
+            self.front = self.storage.next(&node);
+            Some(self.storage.get(&node))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T: 'a, S: Storage<T>> DoubleEndedIterator for Iter<'a, T, S> {
+    fn next_back(&mut self) -> Option<S::Ref<'a>> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        self.len -= 1;
+        unsafe {
+            self.back = self.storage.prev(&node);
+            Some(self.storage.get(&node))
+        }
+    }
+}
+
+impl<'a, T: 'a, S: Storage<T>> ExactSizeIterator for Iter<'a, T, S> {}
+
+/// iterator which moves elements out of list
+pub struct IntoIter<T, S: Storage<T>> {
+    list: DoubleLinkedList<T, S>,
+}
+
+impl<T, S: Storage<T>> Iterator for IntoIter<T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T, S: Storage<T>> DoubleEndedIterator for IntoIter<T, S> {
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, S: Storage<T>> ExactSizeIterator for IntoIter<T, S> {}
+
+/// cursor for editing list in place, every operation takes O(1)
+///
+/// the cursor points at one element, or at the "ghost" position between the last and the first
+/// element, moving from the ghost goes to the first or the last element
+/// ```
+/// use algorithms_rs::lists::double_linked::DoubleLinkedList;
+///
+/// let mut ls: DoubleLinkedList<i32> = (0..5).collect();
+/// let mut cursor = ls.cursor_back_mut();
+/// cursor.move_prev();
+/// assert_eq!(cursor.index(), Some(3));
+/// assert_eq!(cursor.remove_current(), Some(3));
+/// cursor.insert_before(30);
+/// let tail = cursor.split_after();
+/// cursor.splice_before((10..12).collect());
+/// assert_eq!(ls.iter().map(|x| *x).collect::<Vec<_>>(), vec![0, 1, 2, 30, 10, 11, 4]);
+/// assert!(tail.is_empty());
+/// ```
+pub struct CursorMut<'a, T, S: Storage<T>> {
+    list: &'a mut DoubleLinkedList<T, S>,
+    current: Option<S::Node>,
+    index: usize,
+}
+
+impl<'a, T, S: Storage<T>> CursorMut<'a, T, S> {
+    /// return index of current element, `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    /// return current element, `None` at the ghost position
+    pub fn current(&self) -> Option<S::Ref<'_>> {
+        let node = self.current.as_ref()?;
+        Some(unsafe { self.list.storage.get(node) })
+    }
+
+    /// return handle of current element, `None` at the ghost position
+    pub fn handle(&mut self) -> Option<S::Handle> {
+        let node = self.current.as_ref()?;
+        Some(unsafe { self.list.storage.handle(node) })
+    }
+
+    fn next_node(&self) -> Option<S::Node> {
+        match &self.current {
+            Some(node) => unsafe { self.list.storage.next(node) },
+            None => self.list.storage.head(),
+        }
+    }
+
+    fn prev_node(&self) -> Option<S::Node> {
+        match &self.current {
+            Some(node) => unsafe { self.list.storage.prev(node) },
+            None => self.list.storage.tail(),
+        }
+    }
+
+    /// return the element after current one, the first element at the ghost position
+    pub fn peek_next(&self) -> Option<S::Ref<'_>> {
+        let node = self.next_node()?;
+        Some(unsafe { self.list.storage.get(&node) })
+    }
+
+    /// return the element before current one, the last element at the ghost position
+    pub fn peek_prev(&self) -> Option<S::Ref<'_>> {
+        let node = self.prev_node()?;
+        Some(unsafe { self.list.storage.get(&node) })
+    }
+
+    /// move to the next element, the first element at the ghost position
+    pub fn move_next(&mut self) {
+        self.index = match self.current {
+            Some(_) => self.index + 1,
+            None => 0,
+        };
+        self.current = self.next_node();
+    }
+
+    /// move to the previous element, the last element at the ghost position
+    pub fn move_prev(&mut self) {
+        // index of the ghost position is the length of list
+        self.current = self.prev_node();
+        self.index = match self.current {
+            Some(_) => self.index - 1,
+            None => self.list.len(),
+        };
+    }
+
+    /// insert element after current one, at the front at the ghost position
+    pub fn insert_after(&mut self, ele: T) {
+        unsafe {
+            self.list.storage.insert_after(self.current.as_ref(), ele);
+        }
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// insert element before current one, at the back at the ghost position
+    pub fn insert_before(&mut self, ele: T) {
+        unsafe {
+            self.list.storage.insert_before(self.current.as_ref(), ele);
+        }
+        self.index += 1;
+    }
+
+    /// remove current element and move to the next one, do nothing at the ghost position
+    pub fn remove_current(&mut self) -> Option<T> {
+        let next = self.current.as_ref().and_then(|_| self.next_node());
+        let node = mem::replace(&mut self.current, next)?;
+        Some(unsafe { self.list.storage.remove(node) })
+    }
+
+    /// move all elements of `other` after current one, at the front at the ghost position
+    pub fn splice_after(&mut self, other: DoubleLinkedList<T, S>) {
+        if self.current.is_none() {
+            self.index += other.len();
+        }
+        unsafe { self.list.storage.splice_after(self.current.as_ref(), other.storage) }
+    }
+
+    /// move all elements of `other` before current one, at the back at the ghost position
+    pub fn splice_before(&mut self, other: DoubleLinkedList<T, S>) {
+        self.index += other.len();
+        let prev = self.prev_node();
+        unsafe { self.list.storage.splice_after(prev.as_ref(), other.storage) }
+    }
+
+    /// return elements after current one, all elements at the ghost position
+    pub fn split_after(&mut self) -> DoubleLinkedList<T, S> {
+        let count = match self.current {
+            Some(_) => self.list.len() - self.index - 1,
+            None => {
+                self.index = 0;
+                self.list.len()
+            }
+        };
+        let other = unsafe { self.list.storage.split_after(self.current.as_ref(), count) };
+        DoubleLinkedList::from_storage(other)
+    }
+
+    /// return elements before current one, all elements at the ghost position
+    pub fn split_before(&mut self) -> DoubleLinkedList<T, S> {
+        let len = self.list.len();
+        let prev = self.prev_node();
+        let mut front = match self.current {
+            // split at the node before current, then swap so the front part is returned
+            Some(_) => unsafe { self.list.storage.split_after(prev.as_ref(), len - self.index) },
+            None => unsafe { self.list.storage.split_after(None, len) },
+        };
+        if self.current.is_some() {
+            mem::swap(&mut self.list.storage, &mut front);
+        }
+        self.index = 0;
+        DoubleLinkedList::from_storage(front)
+    }
+}
+
+#[cfg(test)]
+fn check_double_linked<S: Storage<i32>>() {
+    let collect = |ls: &DoubleLinkedList<i32, S>| ls.iter().map(|x| *x).collect::<Vec<_>>();
+
+    let mut ls: DoubleLinkedList<i32, S> = DoubleLinkedList::new();
+    assert_eq!(ls.pop_front(), None);
+    assert_eq!(ls.pop_back(), None);
+    assert!(ls.front().is_none());
+    ls.push_back(1);
+    assert_eq!(ls.pop_front(), Some(1));
+    assert!(ls.back().is_none());
+    ls.extend(2..5);
+    ls.push_front(1);
+    ls.push_front(0);
+    assert_eq!(collect(&ls), vec![0, 1, 2, 3, 4]);
+    assert_eq!(ls.iter().rev().map(|x| *x).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    let mut iter = ls.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next().map(|x| *x), Some(0));
+    assert_eq!(iter.next_back().map(|x| *x), Some(4));
+    assert_eq!(iter.map(|x| *x).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(ls.pop_back(), Some(4));
+    assert_eq!(*ls.back().unwrap(), 3);
+    assert!(ls.contains(&2));
+    assert_eq!(format!("{:?}", ls), "[0, 1, 2, 3]");
+    assert_eq!(ls.clone(), ls);
+
+    for at in 0..=4 {
+        let mut left = ls.clone();
+        let mut right = left.split_off(at).unwrap();
+        assert_eq!(left.len(), at);
+        assert_eq!(collect(&right), (at as i32..4).collect::<Vec<_>>());
+        left.append(&mut right);
+        assert!(right.is_empty());
+        assert_eq!(left, ls);
+        // both ends are linked correctly
+        left.push_back(4);
+        left.push_front(-1);
+        assert_eq!(left.into_iter().rev().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0, -1]);
+    }
+    ls.clear();
+    assert!(ls.is_empty());
+    assert!(ls.split_off(1).is_err());
+}
+
+#[cfg(test)]
+fn check_double_linked_cursor<S: Storage<i32>>() {
+    let collect = |ls: &DoubleLinkedList<i32, S>| ls.iter().map(|x| *x).collect::<Vec<_>>();
+
+    let mut ls: DoubleLinkedList<i32, S> = DoubleLinkedList::new();
+    let mut cursor = ls.cursor_front_mut();
+    assert_eq!(cursor.index(), None);
+    assert!(cursor.current().is_none());
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(1);
+    cursor.insert_before(2);
+    cursor.splice_after((-1..1).collect());
+    cursor.splice_before((3..5).collect());
+    assert!(cursor.split_after().iter().map(|x| *x).eq(vec![-1, 0, 1, 2, 3, 4]));
+    cursor.splice_after((0..6).collect());
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.index(), Some(5));
+    assert_eq!(*cursor.current().unwrap(), 5);
+    assert!(cursor.peek_next().is_none());
+    assert_eq!(*cursor.peek_prev().unwrap(), 4);
+    cursor.move_next();
+    assert_eq!(*cursor.peek_next().unwrap(), 0);
+    cursor.move_next();
+    cursor.move_next();
+    // remove current and the cursor moves to the next
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert_eq!(cursor.index(), Some(1));
+    assert_eq!(*cursor.current().unwrap(), 2);
+    cursor.insert_before(10);
+    cursor.insert_after(20);
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(collect(&ls), vec![0, 10, 2, 20, 3, 4, 5]);
+
+    let mut cursor = ls.cursor_back_mut();
+    assert_eq!(cursor.remove_current(), Some(5));
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(*cursor.current().unwrap(), 3);
+    let front = cursor.split_before();
+    assert_eq!(cursor.index(), Some(0));
+    let back = cursor.split_after();
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert!(ls.is_empty());
+    assert_eq!(collect(&front), vec![0, 10, 2, 20]);
+    assert_eq!(collect(&back), vec![4]);
+
+    let mut ls = front;
+    let mut cursor = ls.cursor_front_mut();
+    cursor.move_prev();
+    assert!(cursor.split_before().iter().map(|x| *x).eq(vec![0, 10, 2, 20]));
+    assert!(ls.is_empty());
+    ls.push_back(1);
+    assert_eq!(ls.len(), 1);
+}
+
+#[cfg(test)]
+fn check_double_linked_handle<S: Storage<i32>>() {
+    let mut ls: DoubleLinkedList<i32, S> = (0..5).collect();
+    let first = ls.front_handle().unwrap();
+    let last = ls.back_handle().unwrap();
+    let mut cursor = ls.cursor_front_mut();
+    cursor.move_next();
+    cursor.move_next();
+    let middle = cursor.handle().unwrap();
+    unsafe {
+        assert_eq!(*ls.get(&middle).unwrap(), 2);
+        assert_eq!(ls.remove(&middle), Some(2));
+        assert!(ls.get(&middle).is_none());
+        assert!(!ls.contains_handle(&middle));
+        assert_eq!(ls.remove(&middle.clone()), None);
+    }
+
+    // handles follow their nodes to other list
+    let mut other = ls.split_off(2).unwrap();
+    unsafe {
+        assert_eq!(*other.get(&last).unwrap(), 4);
+        assert!(ls.contains_handle(&first));
+    }
+    ls.append(&mut other);
+    unsafe {
+        assert_eq!(*ls.get(&last).unwrap(), 4);
+        assert_eq!(ls.remove(&last), Some(4));
+    }
+    assert_eq!(*ls.back().unwrap(), 3);
+
+    // handles of both lists stay valid in the destination after splicing at cursor
+    let other: DoubleLinkedList<i32, S> = (10..13).collect();
+    let spliced = other.back_handle().unwrap();
+    let own = ls.front_handle().unwrap();
+    let mut cursor = ls.cursor_front_mut();
+    cursor.move_next();
+    cursor.splice_before(other);
+    assert_eq!(ls.iter().map(|x| *x).collect::<Vec<_>>(), vec![0, 10, 11, 12, 1, 3]);
+    unsafe {
+        assert_eq!(ls.remove(&spliced), Some(12));
+        assert_eq!(ls.remove(&own), Some(0));
+    }
+    let mut cursor = ls.cursor_back_mut();
+    cursor.move_prev();
+    let front = cursor.split_before();
+    assert_eq!(front.iter().map(|x| *x).collect::<Vec<_>>(), vec![10, 11]);
+    let moved = front.front_handle().unwrap();
+    let mut cursor = ls.cursor_front_mut();
+    cursor.splice_after(front);
+    assert_eq!(unsafe { ls.remove(&moved) }, Some(10));
+    assert_eq!(ls.iter().map(|x| *x).collect::<Vec<_>>(), vec![1, 11, 3]);
+
+    // handles outlive the list
+    let mut cursor = ls.cursor_front_mut();
+    let inner = cursor.split_after();
+    drop(ls);
+    let mut ls = inner;
+    ls.push_front(0);
+    assert!(unsafe { ls.get(&first) }.is_none());
+    drop(ls);
+    drop(first);
+}
+
+/// handle of other list is caught in debug builds, even if it's at the same position
+#[cfg(all(test, debug_assertions))]
+fn check_double_linked_foreign_handle<S: Storage<i32>>() {
+    let ls: DoubleLinkedList<i32, S> = (0..5).collect();
+    let other: DoubleLinkedList<i32, S> = (0..5).collect();
+    let other_first = other.front_handle().unwrap();
+    unsafe {
+        ls.get(&other_first);
+    }
+}
+
+/// no element is leaked or dropped twice
+#[cfg(test)]
+fn check_double_linked_drop<S: Storage<std::rc::Rc<()>>>() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut ls: DoubleLinkedList<Rc<()>, S> = (0..10).map(|_| counter.clone()).collect();
+    let handle = ls.back_handle().unwrap();
+    let mut tail = ls.split_off(5).unwrap();
+    tail.pop_front();
+    unsafe {
+        tail.remove(&handle);
+    }
+    let mut iter = tail.into_iter();
+    iter.next_back();
+    drop(iter);
+    let mut cursor = ls.cursor_front_mut();
+    cursor.remove_current();
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(ls);
+    assert_eq!(Rc::strong_count(&counter), 1);
+    drop(handle);
+
+    // long list doesn't overflow stack, it's shorter under miri which is much slower
+    let len = if cfg!(miri) { 1000 } else { 100_000 };
+    let ls: DoubleLinkedList<Rc<()>, S> = (0..len).map(|_| counter.clone()).collect();
+    drop(ls);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn test_double_linked() {
+    check_double_linked::<RawStorage<i32>>();
+    check_double_linked::<rc::RcStorage<i32>>();
+}
+
+#[test]
+fn test_double_linked_cursor() {
+    check_double_linked_cursor::<RawStorage<i32>>();
+    check_double_linked_cursor::<rc::RcStorage<i32>>();
+}
+
+#[test]
+fn test_double_linked_handle() {
+    check_double_linked_handle::<RawStorage<i32>>();
+    check_double_linked_handle::<rc::RcStorage<i32>>();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "handle of other list")]
+fn test_double_linked_foreign_handle_raw() {
+    check_double_linked_foreign_handle::<RawStorage<i32>>();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "handle of other list")]
+fn test_double_linked_foreign_handle_rc() {
+    check_double_linked_foreign_handle::<rc::RcStorage<i32>>();
+}
+
+#[test]
+fn test_double_linked_drop() {
+    check_double_linked_drop::<RawStorage<_>>();
+    check_double_linked_drop::<rc::RcStorage<_>>();
+}
+
+#[test]
+fn test_double_linked_raw_mut() {
+    let mut ls: DoubleLinkedList<i32> = (0..4).collect();
+    let handle = ls.back_handle().unwrap();
+    *ls.front_mut().unwrap() = 10;
+    *unsafe { ls.get_mut(&handle) }.unwrap() += 30;
+    let mut cursor = ls.cursor_front_mut();
+    cursor.move_next();
+    *cursor.current_mut().unwrap() = 20;
+    let mut iter = ls.iter_mut();
+    assert_eq!(iter.len(), 4);
+    let first = iter.next().unwrap();
+    let last = iter.next_back().unwrap();
+    std::mem::swap(first, last);
+    assert_eq!(iter.count(), 2);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![33, 20, 2, 10]);
+    ls.pop_back();
+    assert!(unsafe { ls.get_mut(&handle) }.is_none());
+    assert!(ls.back_mut().is_some());
+}
@@ -0,0 +1,347 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/23 10:20
+//
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{self, AtomicUsize, Ordering};
+
+use crate::lists::double_linked::{CursorMut, DoubleLinkedList, Storage};
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    /// taken out when node is removed, the node itself may live longer for its handles
+    value: MaybeUninit<T>,
+    prev: Link<T>,
+    next: Link<T>,
+    /// cleared when node is removed, so its handles can tell it's gone
+    linked: bool,
+    /// number of handles, plus one while the node is linked
+    refs: AtomicUsize,
+}
+
+impl<T> Node<T> {
+    fn alloc(ele: T) -> NonNull<Node<T>> {
+        let node = Box::new(Node {
+            value: MaybeUninit::new(ele),
+            prev: None,
+            next: None,
+            linked: true,
+            refs: AtomicUsize::new(1),
+        });
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    /// drop one reference and free node after the last one, the value must be taken out before
+    unsafe fn release(node: NonNull<Node<T>>) {
+        if (*node.as_ptr()).refs.fetch_sub(1, Ordering::Release) == 1 {
+            atomic::fence(Ordering::Acquire);
+            drop(Box::from_raw(node.as_ptr()));
+        }
+    }
+}
+
+/// position of node in `RawStorage`
+pub struct NodePtr<T>(NonNull<Node<T>>);
+
+impl<T> Clone for NodePtr<T> {
+    fn clone(&self) -> Self {
+        NodePtr(self.0)
+    }
+}
+
+/// handle of node in `RawStorage`, the node memory is kept until all its handles are dropped,
+/// so a handle of removed node is still safe to check
+pub struct RawHandle<T> {
+    node: NonNull<Node<T>>,
+}
+
+impl<T> Clone for RawHandle<T> {
+    fn clone(&self) -> Self {
+        unsafe {
+            (*self.node.as_ptr()).refs.fetch_add(1, Ordering::Relaxed);
+        }
+        RawHandle { node: self.node }
+    }
+}
+
+impl<T> PartialEq for RawHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T> Eq for RawHandle<T> {}
+
+impl<T> Drop for RawHandle<T> {
+    fn drop(&mut self) {
+        unsafe { Node::release(self.node) }
+    }
+}
+
+/// nodes linked by raw pointers, every node is one allocation
+pub struct RawStorage<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    _mark: PhantomData<Box<Node<T>>>,
+}
+
+unsafe impl<T: Send> Send for RawStorage<T> {}
+
+unsafe impl<T: Sync> Sync for RawStorage<T> {}
+
+impl<T> RawStorage<T> {
+    /// return true if `node` is linked in this storage
+    /// 时间复杂度: O(n)
+    #[cfg(debug_assertions)]
+    unsafe fn holds(&self, node: NonNull<Node<T>>) -> bool {
+        let mut first = node;
+        while let Some(prev) = (*first.as_ptr()).prev {
+            first = prev;
+        }
+        self.head == Some(first)
+    }
+}
+
+impl<T> Default for RawStorage<T> {
+    fn default() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for RawStorage<T> {
+    fn drop(&mut self) {
+        while let Some(node) = self.head {
+            unsafe {
+                self.remove(NodePtr(node));
+            }
+        }
+    }
+}
+
+unsafe impl<T> Storage<T> for RawStorage<T> {
+    type Node = NodePtr<T>;
+    type Handle = RawHandle<T>;
+    type Ref<'a> = &'a T where T: 'a;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn head(&self) -> Option<NodePtr<T>> {
+        self.head.map(NodePtr)
+    }
+
+    fn tail(&self) -> Option<NodePtr<T>> {
+        self.tail.map(NodePtr)
+    }
+
+    unsafe fn next(&self, node: &NodePtr<T>) -> Option<NodePtr<T>> {
+        (*node.0.as_ptr()).next.map(NodePtr)
+    }
+
+    unsafe fn prev(&self, node: &NodePtr<T>) -> Option<NodePtr<T>> {
+        (*node.0.as_ptr()).prev.map(NodePtr)
+    }
+
+    unsafe fn get<'a>(&'a self, node: &NodePtr<T>) -> &'a T {
+        (*node.0.as_ptr()).value.assume_init_ref()
+    }
+
+    unsafe fn insert_after(&mut self, node: Option<&NodePtr<T>>, ele: T) -> NodePtr<T> {
+        let new = Node::alloc(ele);
+        let next = match node {
+            Some(node) => (*node.0.as_ptr()).next.replace(new),
+            None => self.head.replace(new),
+        };
+        (*new.as_ptr()).prev = node.map(|node| node.0);
+        (*new.as_ptr()).next = next;
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(new),
+            None => self.tail = Some(new),
+        }
+        self.len += 1;
+        NodePtr(new)
+    }
+
+    unsafe fn insert_before(&mut self, node: Option<&NodePtr<T>>, ele: T) -> NodePtr<T> {
+        let prev = match node {
+            Some(node) => (*node.0.as_ptr()).prev,
+            None => self.tail,
+        };
+        self.insert_after(prev.map(NodePtr).as_ref(), ele)
+    }
+
+    unsafe fn remove(&mut self, node: NodePtr<T>) -> T {
+        let node = node.0;
+        let prev = (*node.as_ptr()).prev;
+        let next = (*node.as_ptr()).next;
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).prev = prev,
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+        (*node.as_ptr()).linked = false;
+        let value = ptr::read((*node.as_ptr()).value.as_ptr());
+        Node::release(node);
+        value
+    }
+
+    unsafe fn splice_after(&mut self, node: Option<&NodePtr<T>>, mut other: Self) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        let next = match node {
+            Some(node) => (*node.0.as_ptr()).next.replace(head),
+            None => self.head.replace(head),
+        };
+        (*head.as_ptr()).prev = node.map(|node| node.0);
+        (*tail.as_ptr()).next = next;
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(tail),
+            None => self.tail = Some(tail),
+        }
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    unsafe fn split_after(&mut self, node: Option<&NodePtr<T>>, count: usize) -> Self {
+        let mut other = Self::default();
+        if count == 0 {
+            return other;
+        }
+        let head = match node {
+            Some(node) => (*node.0.as_ptr()).next.take(),
+            None => self.head.take(),
+        };
+        (*head.unwrap().as_ptr()).prev = None;
+        other.head = head;
+        other.tail = self.tail;
+        self.tail = node.map(|node| node.0);
+        other.len = count;
+        self.len -= count;
+        other
+    }
+
+    unsafe fn handle(&self, node: &NodePtr<T>) -> RawHandle<T> {
+        (*node.0.as_ptr()).refs.fetch_add(1, Ordering::Relaxed);
+        RawHandle { node: node.0 }
+    }
+
+    unsafe fn resolve(&self, handle: &RawHandle<T>) -> Option<NodePtr<T>> {
+        // the handle keeps node memory alive, so reading the flag is fine even after removal
+        if !(*handle.node.as_ptr()).linked {
+            return None;
+        }
+        #[cfg(debug_assertions)]
+        assert!(self.holds(handle.node), "handle of other list");
+        Some(NodePtr(handle.node))
+    }
+}
+
+impl<T> DoubleLinkedList<T, RawStorage<T>> {
+    /// return the first element as mutable
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.storage.head.map(|node| unsafe { value_mut(node) })
+    }
+
+    /// return the last element as mutable
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.storage.tail.map(|node| unsafe { value_mut(node) })
+    }
+
+    /// return element of `handle` as mutable, `None` if it's removed
+    ///
+    /// # Safety
+    ///
+    /// same as [`get`](DoubleLinkedList::get)
+    pub unsafe fn get_mut(&mut self, handle: &RawHandle<T>) -> Option<&mut T> {
+        let node = self.storage.resolve(handle)?;
+        Some(value_mut(node.0))
+    }
+
+    /// iterate mutable elements from front to back
+    /// ```
+    /// use algorithms_rs::lists::double_linked::DoubleLinkedList;
+    ///
+    /// let mut ls: DoubleLinkedList<i32> = (0..3).collect();
+    /// ls.iter_mut().rev().for_each(|x| *x *= 10);
+    /// *ls.back_mut().unwrap() += 1;
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 10, 21]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.storage.head,
+            back: self.storage.tail,
+            len: self.storage.len,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T, RawStorage<T>> {
+    /// return current element as mutable, `None` at the ghost position
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        let node = self.current.as_ref()?;
+        Some(unsafe { value_mut(node.0) })
+    }
+}
+
+/// return value of linked node, only the value is borrowed so links can still be changed
+unsafe fn value_mut<'a, T>(node: NonNull<Node<T>>) -> &'a mut T {
+    (*node.as_ptr()).value.assume_init_mut()
+}
+
+/// iterator of mutable elements from front to back
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _mark: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.front.map(|node| unsafe {
+            self.len -= 1;
+            self.front = (*node.as_ptr()).next;
+            value_mut(node)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.back.map(|node| unsafe {
+            self.len -= 1;
+            self.back = (*node.as_ptr()).prev;
+            value_mut(node)
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
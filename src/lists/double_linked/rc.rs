@@ -0,0 +1,246 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/23 15:05
+//
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+
+use crate::lists::double_linked::Storage;
+
+/// node owns the next node and points back to the previous one weakly, so there is no cycle
+struct Node<T> {
+    value: T,
+    prev: RefCell<Weak<Node<T>>>,
+    next: RefCell<Option<Rc<Node<T>>>>,
+}
+
+/// position of node in `RcStorage`, it's weak so cursors and iterators left alive don't keep
+/// removed node from being unwrapped
+pub struct NodeRc<T>(Weak<Node<T>>);
+
+impl<T> NodeRc<T> {
+    fn new(node: &Rc<Node<T>>) -> Self {
+        NodeRc(Rc::downgrade(node))
+    }
+
+    /// the node is alive while it's in storage
+    fn rc(&self) -> Rc<Node<T>> {
+        self.0.upgrade().expect("node is not in storage")
+    }
+}
+
+impl<T> Clone for NodeRc<T> {
+    fn clone(&self) -> Self {
+        NodeRc(self.0.clone())
+    }
+}
+
+/// handle of node in `RcStorage`, it doesn't keep the node alive
+pub struct RcHandle<T>(Weak<Node<T>>);
+
+impl<T> Clone for RcHandle<T> {
+    fn clone(&self) -> Self {
+        RcHandle(self.0.clone())
+    }
+}
+
+/// reference of element in `RcStorage`, the list is borrowed while it's alive
+pub struct RcRef<'a, T> {
+    node: Rc<Node<T>>,
+    _mark: PhantomData<&'a T>,
+}
+
+impl<'a, T> Drop for RcRef<'a, T> {
+    /// having `Drop` keeps the list borrowed until the reference is dropped,
+    /// otherwise the list may be changed while the node is still shared by it
+    fn drop(&mut self) {}
+}
+
+impl<'a, T> Deref for RcRef<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node.value
+    }
+}
+
+/// nodes linked by `Rc` and `Weak`, links are changed through `RefCell`
+///
+/// a `&mut T` can't be given out from node shared by `Rc`, so elements can't be changed in place,
+/// wrap them in `Cell` or `RefCell` if it's needed
+pub struct RcStorage<T> {
+    head: Option<Rc<Node<T>>>,
+    tail: Option<Rc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> RcStorage<T> {
+    /// return true if `node` is linked in this storage
+    /// 时间复杂度: O(n)
+    #[cfg(debug_assertions)]
+    fn holds(&self, node: &Rc<Node<T>>) -> bool {
+        let mut first = node.clone();
+        loop {
+            let prev = first.prev.borrow().upgrade();
+            match prev {
+                Some(prev) => first = prev,
+                None => break,
+            }
+        }
+        self.head.as_ref().is_some_and(|head| Rc::ptr_eq(head, &first))
+    }
+}
+
+impl<T> Default for RcStorage<T> {
+    fn default() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> Drop for RcStorage<T> {
+    /// unlink nodes one by one, dropping the chain of `Rc` recursively overflows stack for long list
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut node = self.head.take();
+        while let Some(n) = node {
+            node = n.next.borrow_mut().take();
+        }
+    }
+}
+
+unsafe impl<T> Storage<T> for RcStorage<T> {
+    type Node = NodeRc<T>;
+    type Handle = RcHandle<T>;
+    type Ref<'a> = RcRef<'a, T> where T: 'a;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn head(&self) -> Option<NodeRc<T>> {
+        self.head.as_ref().map(NodeRc::new)
+    }
+
+    fn tail(&self) -> Option<NodeRc<T>> {
+        self.tail.as_ref().map(NodeRc::new)
+    }
+
+    unsafe fn next(&self, node: &NodeRc<T>) -> Option<NodeRc<T>> {
+        node.rc().next.borrow().as_ref().map(NodeRc::new)
+    }
+
+    unsafe fn prev(&self, node: &NodeRc<T>) -> Option<NodeRc<T>> {
+        node.rc().prev.borrow().upgrade().as_ref().map(NodeRc::new)
+    }
+
+    unsafe fn get<'a>(&'a self, node: &NodeRc<T>) -> RcRef<'a, T> {
+        RcRef {
+            node: node.rc(),
+            _mark: PhantomData,
+        }
+    }
+
+    unsafe fn insert_after(&mut self, node: Option<&NodeRc<T>>, ele: T) -> NodeRc<T> {
+        let node = node.map(NodeRc::rc);
+        let new = Rc::new(Node {
+            value: ele,
+            prev: RefCell::new(node.as_ref().map(Rc::downgrade).unwrap_or_default()),
+            next: RefCell::new(None),
+        });
+        let next = match &node {
+            Some(node) => node.next.replace(Some(new.clone())),
+            None => self.head.replace(new.clone()),
+        };
+        match &next {
+            Some(next) => *next.prev.borrow_mut() = Rc::downgrade(&new),
+            None => self.tail = Some(new.clone()),
+        }
+        *new.next.borrow_mut() = next;
+        self.len += 1;
+        NodeRc::new(&new)
+    }
+
+    unsafe fn insert_before(&mut self, node: Option<&NodeRc<T>>, ele: T) -> NodeRc<T> {
+        let prev = match node {
+            Some(node) => self.prev(node),
+            None => self.tail(),
+        };
+        self.insert_after(prev.as_ref(), ele)
+    }
+
+    unsafe fn remove(&mut self, node: NodeRc<T>) -> T {
+        let node = node.rc();
+        let prev = node.prev.borrow().upgrade();
+        let next = node.next.borrow_mut().take();
+        match &next {
+            Some(next) => *next.prev.borrow_mut() = node.prev.borrow().clone(),
+            None => self.tail = prev.clone(),
+        }
+        match prev {
+            Some(prev) => *prev.next.borrow_mut() = next,
+            None => self.head = next,
+        }
+        self.len -= 1;
+        // every strong reference is released now, except `node` itself,
+        // `RcRef` can't be alive since the list is borrowed by it
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.value,
+            Err(_) => panic!("removed node is still referenced"),
+        }
+    }
+
+    unsafe fn splice_after(&mut self, node: Option<&NodeRc<T>>, mut other: Self) {
+        let node = node.map(NodeRc::rc);
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        *head.prev.borrow_mut() = node.as_ref().map(Rc::downgrade).unwrap_or_default();
+        let next = match &node {
+            Some(node) => node.next.replace(Some(head)),
+            None => self.head.replace(head),
+        };
+        match &next {
+            Some(next) => *next.prev.borrow_mut() = Rc::downgrade(&tail),
+            None => self.tail = Some(tail.clone()),
+        }
+        *tail.next.borrow_mut() = next;
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    unsafe fn split_after(&mut self, node: Option<&NodeRc<T>>, count: usize) -> Self {
+        let mut other = Self::default();
+        if count == 0 {
+            return other;
+        }
+        let node = node.map(NodeRc::rc);
+        let head = match &node {
+            Some(node) => node.next.borrow_mut().take(),
+            None => self.head.take(),
+        };
+        *head.as_ref().unwrap().prev.borrow_mut() = Weak::new();
+        other.tail = std::mem::replace(&mut self.tail, node);
+        other.len = count;
+        self.len -= count;
+        other.head = head;
+        other
+    }
+
+    unsafe fn handle(&self, node: &NodeRc<T>) -> RcHandle<T> {
+        RcHandle(node.0.clone())
+    }
+
+    unsafe fn resolve(&self, handle: &RcHandle<T>) -> Option<NodeRc<T>> {
+        // removed node is dropped at once, so its handle can't be upgraded
+        let node = handle.0.upgrade()?;
+        #[cfg(debug_assertions)]
+        assert!(self.holds(&node), "handle of other list");
+        Some(NodeRc::new(&node))
+    }
+}
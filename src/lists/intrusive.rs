@@ -0,0 +1,659 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/24 14:10
+//
+use std::fmt;
+
+use crate::error;
+use crate::lists::double_linked::next_id;
+
+/// link field embedded in element of `IntrusiveList`
+///
+/// cloning a link gives an unlinked one, so a clone of linked element doesn't take its place in list
+pub struct Link {
+    prev: Option<usize>,
+    next: Option<usize>,
+    /// id of list the element is linked in, 0 if it's not linked
+    owner: usize,
+}
+
+impl Link {
+    pub fn new() -> Self {
+        Self {
+            prev: None,
+            next: None,
+            owner: 0,
+        }
+    }
+
+    /// return true if the element is linked in any list
+    pub fn is_linked(&self) -> bool {
+        self.owner != 0
+    }
+
+    /// unlink the element without touching the list, only for elements left by a dropped list,
+    /// resetting link of an element in a live list breaks that list
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for Link {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Link")
+            .field("prev", &self.prev)
+            .field("next", &self.next)
+            .field("linked", &self.is_linked())
+            .finish()
+    }
+}
+
+/// element which embeds its own `Link`
+pub trait Linked {
+    fn link(&self) -> &Link;
+
+    fn link_mut(&mut self) -> &mut Link;
+}
+
+/// double linked list over elements of a slice owned by caller, elements are named by their
+/// index in the slice and the links are stored in elements themselves, so the list allocates nothing
+///
+/// every method takes the slice, which must be the same one for the whole life of list.
+/// a wrong slice or a link changed through `link_mut` only gives wrong answers or panics,
+/// there is no unsafe code here
+///
+/// the list can't reach the slice when it's dropped, so call `clear` before dropping non-empty list,
+/// otherwise its elements stay linked to a list that is gone until their links are `reset`
+/// ```
+/// use algorithms_rs::lists::intrusive::{IntrusiveList, Link, Linked};
+///
+/// struct Entry {
+///     key: &'static str,
+///     link: Link,
+/// }
+///
+/// impl Linked for Entry {
+///     fn link(&self) -> &Link { &self.link }
+///     fn link_mut(&mut self) -> &mut Link { &mut self.link }
+/// }
+///
+/// let mut entries: Vec<Entry> = ["a", "b", "c"].iter()
+///     .map(|&key| Entry { key, link: Link::new() })
+///     .collect();
+/// // least recently used entry is at the back
+/// let mut lru = IntrusiveList::new();
+/// for i in 0..entries.len() {
+///     lru.push_front(&mut entries, i).unwrap();
+/// }
+/// lru.move_to_front(&mut entries, 0).unwrap();
+/// assert_eq!(lru.pop_back(&mut entries), Some(1));
+/// assert!(lru.push_back(&mut entries, 0).is_err());
+/// assert_eq!(lru.iter(&entries).map(|e| e.key).collect::<Vec<_>>(), vec!["a", "c"]);
+/// ```
+pub struct IntrusiveList {
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    id: usize,
+}
+
+impl IntrusiveList {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            id: next_id(),
+        }
+    }
+
+    /// return number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// return index of the first element
+    pub fn front(&self) -> Option<usize> {
+        self.head
+    }
+
+    /// return index of the last element
+    pub fn back(&self) -> Option<usize> {
+        self.tail
+    }
+
+    /// return true if element `i` is linked in this list
+    pub fn contains<T: Linked>(&self, items: &[T], i: usize) -> bool {
+        items.get(i).is_some_and(|item| item.link().owner == self.id)
+    }
+
+    /// return index of element after `i`, `None` if `i` is the last one or not in this list
+    pub fn next<T: Linked>(&self, items: &[T], i: usize) -> Option<usize> {
+        if self.contains(items, i) { items[i].link().next } else { None }
+    }
+
+    /// return index of element before `i`, `None` if `i` is the first one or not in this list
+    pub fn prev<T: Linked>(&self, items: &[T], i: usize) -> Option<usize> {
+        if self.contains(items, i) { items[i].link().prev } else { None }
+    }
+
+    fn check_unlinked<T: Linked>(items: &[T], i: usize) -> Result<(), error::Error> {
+        match items.get(i) {
+            None => Err(error::Error::IndexOfBounds),
+            Some(item) if item.link().is_linked() => Err(error::Error::AlreadyLinked),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn check_linked<T: Linked>(&self, items: &[T], i: usize) -> Result<(), error::Error> {
+        match items.get(i) {
+            None => Err(error::Error::IndexOfBounds),
+            Some(item) if item.link().owner != self.id => Err(error::Error::NotLinked),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// link element `i` between `prev` and `next`, which must be adjacent
+    fn attach<T: Linked>(&mut self, items: &mut [T], i: usize, prev: Option<usize>, next: Option<usize>) {
+        *items[i].link_mut() = Link { prev, next, owner: self.id };
+        match prev {
+            Some(prev) => items[prev].link_mut().next = Some(i),
+            None => self.head = Some(i),
+        }
+        match next {
+            Some(next) => items[next].link_mut().prev = Some(i),
+            None => self.tail = Some(i),
+        }
+        self.len += 1;
+    }
+
+    /// unlink element `i` which is linked in this list
+    fn detach<T: Linked>(&mut self, items: &mut [T], i: usize) {
+        let Link { prev, next, .. } = std::mem::take(items[i].link_mut());
+        match prev {
+            Some(prev) => items[prev].link_mut().next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => items[next].link_mut().prev = prev,
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+    }
+
+    /// link element `i` at the front, it must not be linked in any list
+    /// 时间复杂度: O(1)
+    pub fn push_front<T: Linked>(&mut self, items: &mut [T], i: usize) -> Result<(), error::Error> {
+        Self::check_unlinked(items, i)?;
+        self.attach(items, i, None, self.head);
+        Ok(())
+    }
+
+    /// link element `i` at the back, it must not be linked in any list
+    /// 时间复杂度: O(1)
+    pub fn push_back<T: Linked>(&mut self, items: &mut [T], i: usize) -> Result<(), error::Error> {
+        Self::check_unlinked(items, i)?;
+        self.attach(items, i, self.tail, None);
+        Ok(())
+    }
+
+    /// unlink the first element and return its index
+    /// 时间复杂度: O(1)
+    pub fn pop_front<T: Linked>(&mut self, items: &mut [T]) -> Option<usize> {
+        let i = self.head?;
+        self.detach(items, i);
+        Some(i)
+    }
+
+    /// unlink the last element and return its index
+    /// 时间复杂度: O(1)
+    pub fn pop_back<T: Linked>(&mut self, items: &mut [T]) -> Option<usize> {
+        let i = self.tail?;
+        self.detach(items, i);
+        Some(i)
+    }
+
+    /// unlink element `i`, it must be linked in this list
+    /// 时间复杂度: O(1)
+    pub fn remove<T: Linked>(&mut self, items: &mut [T], i: usize) -> Result<(), error::Error> {
+        self.check_linked(items, i)?;
+        self.detach(items, i);
+        Ok(())
+    }
+
+    /// move element `i` to the front, it must be linked in this list
+    /// 时间复杂度: O(1)
+    pub fn move_to_front<T: Linked>(&mut self, items: &mut [T], i: usize) -> Result<(), error::Error> {
+        self.remove(items, i)?;
+        self.attach(items, i, None, self.head);
+        Ok(())
+    }
+
+    /// move element `i` to the back, it must be linked in this list
+    /// 时间复杂度: O(1)
+    pub fn move_to_back<T: Linked>(&mut self, items: &mut [T], i: usize) -> Result<(), error::Error> {
+        self.remove(items, i)?;
+        self.attach(items, i, self.tail, None);
+        Ok(())
+    }
+
+    /// unlink all elements
+    /// 时间复杂度: O(n)
+    pub fn clear<T: Linked>(&mut self, items: &mut [T]) {
+        while self.pop_front(items).is_some() {}
+    }
+
+    /// iterate elements from front to back
+    pub fn iter<'a, T: Linked>(&self, items: &'a [T]) -> Iter<'a, T> {
+        Iter {
+            items,
+            front: self.head,
+            back: self.tail,
+            len: self.len,
+        }
+    }
+
+    /// iterate indices of elements from front to back
+    pub fn indices<'a, T: Linked>(&self, items: &'a [T]) -> Indices<'a, T> {
+        Indices { iter: self.iter(items) }
+    }
+
+    /// call `f` with every element from front to back as mutable
+    ///
+    /// there is no `iter_mut`, links can be changed by caller so the list can't promise
+    /// that no element is visited twice, which a safe iterator of `&mut T` requires
+    pub fn for_each_mut<T: Linked, F>(&self, items: &mut [T], mut f: F)
+        where F: FnMut(&mut T)
+    {
+        let mut cur = self.head;
+        for _ in 0..self.len {
+            match cur {
+                Some(i) => {
+                    f(&mut items[i]);
+                    cur = items[i].link().next;
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// return cursor at the first element, or at the "ghost" position if list is empty
+    pub fn cursor_front_mut<'a, T: Linked>(&'a mut self, items: &'a mut [T]) -> CursorMut<'a, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+            items,
+        }
+    }
+
+    /// return cursor at the last element, or at the "ghost" position if list is empty
+    pub fn cursor_back_mut<'a, T: Linked>(&'a mut self, items: &'a mut [T]) -> CursorMut<'a, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+            items,
+        }
+    }
+}
+
+impl Default for IntrusiveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for IntrusiveList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntrusiveList")
+            .field("head", &self.head)
+            .field("tail", &self.tail)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// iterator of elements from front to back
+pub struct Iter<'a, T> {
+    items: &'a [T],
+    front: Option<usize>,
+    back: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T: Linked> Iter<'a, T> {
+    fn next_index(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let i = self.front?;
+        self.len -= 1;
+        self.front = self.items[i].link().next;
+        Some(i)
+    }
+
+    fn next_back_index(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let i = self.back?;
+        self.len -= 1;
+        self.back = self.items[i].link().prev;
+        Some(i)
+    }
+}
+
+impl<'a, T: Linked> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let items = self.items;
+        self.next_index().map(|i| &items[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len))
+    }
+}
+
+impl<'a, T: Linked> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        let items = self.items;
+        self.next_back_index().map(|i| &items[i])
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// iterator of element indices from front to back
+pub struct Indices<'a, T> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T: Linked> Iterator for Indices<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.iter.next_index()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: Linked> DoubleEndedIterator for Indices<'a, T> {
+    fn next_back(&mut self) -> Option<usize> {
+        self.iter.next_back_index()
+    }
+}
+
+/// cursor for editing list in place, it has the methods of `linked::CursorMut`,
+/// elements are inserted by index and removed ones are returned by index
+/// ```
+/// use algorithms_rs::lists::intrusive::{IntrusiveList, Link, Linked};
+///
+/// #[derive(Default)]
+/// struct Node(u32, Link);
+///
+/// impl Linked for Node {
+///     fn link(&self) -> &Link { &self.1 }
+///     fn link_mut(&mut self) -> &mut Link { &mut self.1 }
+/// }
+///
+/// let mut nodes: Vec<Node> = (0..5).map(|x| Node(x * 10, Link::new())).collect();
+/// let mut ls = IntrusiveList::new();
+/// for i in 0..3 {
+///     ls.push_back(&mut nodes, i).unwrap();
+/// }
+/// let mut cursor = ls.cursor_front_mut(&mut nodes);
+/// cursor.move_next();
+/// assert_eq!(cursor.remove_current(), Some(1));
+/// cursor.insert_before(4).unwrap();
+/// cursor.current().unwrap().0 += 1;
+/// assert_eq!(ls.indices(&nodes).collect::<Vec<_>>(), vec![0, 4, 2]);
+/// assert_eq!(ls.iter(&nodes).map(|n| n.0).collect::<Vec<_>>(), vec![0, 40, 21]);
+/// ```
+pub struct CursorMut<'a, T> {
+    list: &'a mut IntrusiveList,
+    items: &'a mut [T],
+    current: Option<usize>,
+    index: usize,
+}
+
+impl<'a, T: Linked> CursorMut<'a, T> {
+    /// return position of current element in list, `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// return index of current element in slice, `None` at the ghost position
+    pub fn slot(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// return current element, `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut T> {
+        let i = self.current?;
+        Some(&mut self.items[i])
+    }
+
+    fn next_slot(&self) -> Option<usize> {
+        match self.current {
+            Some(i) => self.items[i].link().next,
+            None => self.list.head,
+        }
+    }
+
+    fn prev_slot(&self) -> Option<usize> {
+        match self.current {
+            Some(i) => self.items[i].link().prev,
+            None => self.list.tail,
+        }
+    }
+
+    /// return the element after current one, the first element at the ghost position
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let i = self.next_slot()?;
+        Some(&mut self.items[i])
+    }
+
+    /// return the element before current one, the last element at the ghost position
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let i = self.prev_slot()?;
+        Some(&mut self.items[i])
+    }
+
+    /// move to the next element, the first element at the ghost position
+    pub fn move_next(&mut self) {
+        self.index = match self.current {
+            Some(_) => self.index + 1,
+            None => 0,
+        };
+        self.current = self.next_slot();
+    }
+
+    /// move to the previous element, the last element at the ghost position
+    pub fn move_prev(&mut self) {
+        // index of the ghost position is the length of list
+        self.current = self.prev_slot();
+        self.index = match self.current {
+            Some(_) => self.index - 1,
+            None => self.list.len,
+        };
+    }
+
+    /// link element `i` after current one, at the front at the ghost position
+    pub fn insert_after(&mut self, i: usize) -> Result<(), error::Error> {
+        IntrusiveList::check_unlinked(self.items, i)?;
+        let next = self.next_slot();
+        if self.current.is_none() {
+            self.index += 1;
+        }
+        self.list.attach(self.items, i, self.current, next);
+        Ok(())
+    }
+
+    /// link element `i` before current one, at the back at the ghost position
+    pub fn insert_before(&mut self, i: usize) -> Result<(), error::Error> {
+        IntrusiveList::check_unlinked(self.items, i)?;
+        let prev = self.prev_slot();
+        self.index += 1;
+        self.list.attach(self.items, i, prev, self.current);
+        Ok(())
+    }
+
+    /// unlink current element, return its index and move to the next one,
+    /// do nothing at the ghost position
+    pub fn remove_current(&mut self) -> Option<usize> {
+        let i = self.current?;
+        self.current = self.items[i].link().next;
+        self.list.detach(self.items, i);
+        Some(i)
+    }
+
+    /// move all elements of `other` after current one, at the front at the ghost position,
+    /// `other` must be a list over the same slice
+    /// 时间复杂度: O(m)
+    pub fn splice_after(&mut self, mut other: IntrusiveList) {
+        while let Some(i) = other.pop_back(self.items) {
+            // `i` is unlinked right now, so it can't fail
+            let _ = self.insert_after(i);
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, Default, Clone)]
+struct Item(i32, Link);
+
+#[cfg(test)]
+impl Linked for Item {
+    fn link(&self) -> &Link {
+        &self.1
+    }
+
+    fn link_mut(&mut self) -> &mut Link {
+        &mut self.1
+    }
+}
+
+#[test]
+fn test_intrusive_list() {
+    let mut items: Vec<Item> = (0..6).map(|x| Item(x, Link::new())).collect();
+    let mut ls = IntrusiveList::new();
+    assert_eq!(ls.pop_front(&mut items), None);
+    assert!(ls.push_back(&mut items, 6).is_err());
+    assert!(ls.remove(&mut items, 0).is_err());
+    for i in 0..4 {
+        ls.push_back(&mut items, i).unwrap();
+    }
+    ls.push_front(&mut items, 4).unwrap();
+    assert!(ls.push_front(&mut items, 4).is_err());
+    // linked in another list
+    let mut other = IntrusiveList::new();
+    assert!(other.push_back(&mut items, 0).is_err());
+    assert!(other.remove(&mut items, 0).is_err());
+    assert!(!other.contains(&items, 0));
+    // clone of linked element is unlinked
+    items.push(items[0].clone());
+    other.push_back(&mut items, 6).unwrap();
+    other.push_back(&mut items, 5).unwrap();
+
+    assert_eq!(ls.len(), 5);
+    assert_eq!(ls.indices(&items).collect::<Vec<_>>(), vec![4, 0, 1, 2, 3]);
+    assert_eq!(ls.indices(&items).rev().collect::<Vec<_>>(), vec![3, 2, 1, 0, 4]);
+    ls.move_to_back(&mut items, 4).unwrap();
+    ls.move_to_front(&mut items, 2).unwrap();
+    assert!(ls.move_to_front(&mut items, 5).is_err());
+    assert_eq!(ls.front(), Some(2));
+    assert_eq!(ls.back(), Some(4));
+    assert_eq!(ls.next(&items, 2), Some(0));
+    assert_eq!(ls.prev(&items, 2), None);
+    assert_eq!(ls.next(&items, 6), None);
+    ls.remove(&mut items, 1).unwrap();
+    assert!(!items[1].link().is_linked());
+    assert_eq!(ls.pop_back(&mut items), Some(4));
+    ls.for_each_mut(&mut items, |item| item.0 *= 10);
+    assert_eq!(ls.iter(&items).map(|item| item.0).collect::<Vec<_>>(), vec![20, 0, 30]);
+    assert_eq!(items[1].0, 1);
+
+    let mut cursor = ls.cursor_back_mut(&mut items);
+    assert_eq!(cursor.slot(), Some(3));
+    cursor.splice_after(other);
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(3));
+    assert_eq!(cursor.current().map(|item| item.0), Some(0));
+    assert_eq!(cursor.remove_current(), Some(6));
+    cursor.move_prev();
+    assert_eq!(cursor.peek_prev().map(|item| item.0), Some(0));
+    assert!(cursor.insert_before(3).is_err());
+    cursor.insert_before(4).unwrap();
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.index(), None);
+    cursor.insert_after(1).unwrap();
+    assert_eq!(cursor.peek_next().map(|item| item.0), Some(1));
+    assert_eq!(ls.indices(&items).collect::<Vec<_>>(), vec![1, 2, 0, 4, 3, 5]);
+    assert!(ls.contains(&items, 5));
+    ls.clear(&mut items);
+    assert!(ls.is_empty());
+    assert!(items.iter().all(|item| !item.link().is_linked()));
+}
+
+/// links broken by caller never make the list loop forever
+#[test]
+fn test_intrusive_broken_link() {
+    let mut items: Vec<Item> = vec![Item::default(); 3];
+    let mut ls = IntrusiveList::new();
+    for i in 0..3 {
+        ls.push_back(&mut items, i).unwrap();
+    }
+    items[2].1 = items[0].1.clone();
+    items.swap(0, 1);
+    let mut count = 0;
+    ls.for_each_mut(&mut items, |_| count += 1);
+    assert!(count <= 3);
+    assert!(ls.iter(&items).count() <= 3);
+}
+
+#[test]
+fn test_intrusive_dropped_list() {
+    let mut items: Vec<Item> = (0..3).map(|x| Item(x, Link::new())).collect();
+    let mut ls = IntrusiveList::new();
+    for i in 0..3 {
+        ls.push_back(&mut items, i).unwrap();
+    }
+    // the old list is dropped, its elements are still linked to it
+    ls = IntrusiveList::new();
+    assert!(ls.push_back(&mut items, 0).is_err());
+    items.iter_mut().for_each(|item| item.link_mut().reset());
+    assert!(items.iter().all(|item| !item.link().is_linked()));
+    for i in (0..3).rev() {
+        ls.push_back(&mut items, i).unwrap();
+    }
+    assert_eq!(ls.iter(&items).map(|item| item.0).collect::<Vec<_>>(), vec![2, 1, 0]);
+    ls.clear(&mut items);
+    assert!(items.iter().all(|item| !item.link().is_linked()));
+}
@@ -2,87 +2,597 @@
 // Create by VenmoSnake 2020/7/13 17:53
 //
 
-use std::fmt::Debug;
-use std::rc::Rc;
+use std::fmt::{self, Debug};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use crate::error;
+
+type Link<T> = Option<NonNull<Node<T>>>;
 
-#[derive(Debug, Eq, PartialEq, Clone)]
 struct Node<V> {
     value: V,
-    next: Option<Box<Node<V>>>,
+    next: Link<V>,
 }
 
 impl<V> Node<V> {
-    pub fn new(val: V) -> Self {
-        Self {
+    /// allocate node on heap and leak it, it's freed by `Node::free`
+    pub fn alloc(val: V, next: Link<V>) -> NonNull<Node<V>> {
+        let node = Box::new(Self {
             value: val,
-            next: None,
-        }
+            next,
+        });
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    /// free node allocated by `Node::alloc` and return its value
+    unsafe fn free(node: NonNull<Node<V>>) -> V {
+        Box::from_raw(node.as_ptr()).value
     }
 }
 
+/// singly linked list, keeps pointer of the last node so append is O(1)
+/// ```
+/// use algorithms_rs::lists::linked::LinkedList;
+/// let mut ls = LinkedList::new();
+/// ls.push_back(2);
+/// ls.push_back(3);
+/// ls.push_front(1);
+/// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// assert_eq!(ls.pop_front(), Some(1));
+/// assert_eq!(ls.pop_back(), Some(3));
+/// ```
 pub struct LinkedList<T> {
-    head: Option<Box<Node<T>>>,
+    head: Link<T>,
+    tail: Link<T>,
     size: usize,
+    _mark: PhantomData<Box<Node<T>>>,
 }
 
-impl<T: Debug> LinkedList<T> {
+unsafe impl<T: Send> Send for LinkedList<T> {}
+
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> LinkedList<T> {
     pub fn new() -> Self {
         Self {
             head: None,
+            tail: None,
             size: 0,
+            _mark: PhantomData,
         }
     }
 
+    /// return number of elements
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// drop all elements
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// 时间复杂度: O(1)
     pub fn push_front(&mut self, ele: T) {
-        if self.head.is_none() {
-            self.head = Some(Box::new(Node::new(ele)));
-        } else {
-            let mut node = Box::new(Node::new(ele));
-            node.next = self.head.take();
-            self.head = Some(node);
+        let node = Node::alloc(ele, self.head);
+        if self.tail.is_none() {
+            self.tail = Some(node);
         }
+        self.head = Some(node);
         self.size += 1;
     }
 
+    /// 时间复杂度: O(1)
     pub fn push_back(&mut self, ele: T) {
-        if let Some(mut node) = self.head.as_mut() {
-            while let Some(ref mut n) = node.next {
-                node = n;
-            }
-            node.next = Some(Box::new(Node::new(ele)));
-        } else {
-            self.head = Some(Box::new(Node::new(ele)));
+        let node = Node::alloc(ele, None);
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
         }
+        self.tail = Some(node);
         self.size += 1;
     }
 
+    /// remove the first element
+    /// 时间复杂度: O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| unsafe {
+            self.head = (*head.as_ptr()).next;
+            if self.head.is_none() {
+                self.tail = None;
+            }
+            self.size -= 1;
+            Node::free(head)
+        })
+    }
+
+    /// remove the last element, the node before it has to be found from head
+    /// 时间复杂度: O(n)
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        if self.head == self.tail {
+            return self.pop_front();
+        }
+        unsafe {
+            let mut prev = self.head.unwrap();
+            while (*prev.as_ptr()).next != Some(tail) {
+                prev = (*prev.as_ptr()).next.unwrap();
+            }
+            (*prev.as_ptr()).next = None;
+            self.tail = Some(prev);
+            self.size -= 1;
+            Some(Node::free(tail))
+        }
+    }
+
+    /// same as `pop_back`
     pub fn pop(&mut self) -> Option<T> {
-        // let mut res = match self.head.as_mut() {
-        //     Some(node) => {
-        //         let mut cur = node;
-        //         while let Some(n) = cur.next{
-        //             cur = n.next.as_mut().unwrap();
-        //         }
-        //         Some(cur)
-        //     }
-        //     None => None
-        // };
-        // let cur = res.as_mut().unwrap().next.take();
-        // Some(cur.unwrap().value)
-        None
+        self.pop_back()
+    }
+
+    /// return the first element
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// return the first element as mutable
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// return the last element
+    pub fn peek_back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// return the last element as mutable
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// iterate elements from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            len: self.size,
+            _mark: PhantomData,
+        }
+    }
+
+    /// iterate mutable elements from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            len: self.size,
+            _mark: PhantomData,
+        }
+    }
+
+    /// return cursor at the first element, or at the "ghost" position if list is empty
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            prev: None,
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// return true if list contains `ele`
+    pub fn contains(&self, ele: &T) -> bool
+        where T: PartialEq
+    {
+        self.iter().any(|x| x == ele)
+    }
+
+    /// reverse the list in place
+    /// 时间复杂度: O(n)
+    /// ```
+    /// use algorithms_rs::lists::linked::LinkedList;
+    /// let mut ls: LinkedList<i32> = (0..4).collect();
+    /// ls.reverse();
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1, 0]);
+    /// assert_eq!(ls.peek_back(), Some(&0));
+    /// ```
+    pub fn reverse(&mut self) {
+        let mut prev = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).next;
+                (*node.as_ptr()).next = prev;
+            }
+            prev = Some(node);
+        }
+        self.tail = self.head;
+        self.head = prev;
+    }
+
+    /// move all elements of `other` to the back, `other` becomes empty
+    /// 时间复杂度: O(1)
+    /// ```
+    /// use algorithms_rs::lists::linked::LinkedList;
+    /// let mut ls: LinkedList<i32> = (0..2).collect();
+    /// let mut other: LinkedList<i32> = (2..4).collect();
+    /// ls.append(&mut other);
+    /// assert!(other.is_empty());
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let head = match other.head.take() {
+            Some(head) => head,
+            None => return,
+        };
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(head) },
+            None => self.head = Some(head),
+        }
+        self.tail = other.tail.take();
+        self.size += other.size;
+        other.size = 0;
+    }
+
+    /// split list into two at `at`, return elements after `at` and keep `[0, at)`
+    /// 时间复杂度: O(at)
+    /// ```
+    /// use algorithms_rs::lists::linked::LinkedList;
+    /// let mut ls: LinkedList<i32> = (0..5).collect();
+    /// let tail = ls.split_off(3).unwrap();
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(tail.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+    /// assert!(ls.split_off(4).is_err());
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Result<LinkedList<T>, error::Error> {
+        if at > self.size {
+            return Err(error::Error::IndexOfBounds);
+        }
+        if at == 0 {
+            return Ok(std::mem::take(self));
+        }
+        let mut other = LinkedList::new();
+        unsafe {
+            let mut last = self.head.unwrap();
+            for _ in 1..at {
+                last = (*last.as_ptr()).next.unwrap();
+            }
+            other.head = (*last.as_ptr()).next.take();
+            if other.head.is_some() {
+                other.tail = self.tail;
+                self.tail = Some(last);
+            }
+        }
+        other.size = self.size - at;
+        self.size = at;
+        Ok(other)
+    }
+
+    /// remove all elements matching `pred` and return how many are removed
+    /// 时间复杂度: O(n)
+    /// ```
+    /// use algorithms_rs::lists::linked::LinkedList;
+    /// let mut ls: LinkedList<i32> = (0..10).collect();
+    /// assert_eq!(ls.remove_if(|x| x % 3 != 0), 6);
+    /// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 3, 6, 9]);
+    /// ```
+    pub fn remove_if<F>(&mut self, mut pred: F) -> usize
+        where F: FnMut(&T) -> bool
+    {
+        let mut removed = 0;
+        let mut prev: Link<T> = None;
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).next;
+                // the node is unlinked before it's dropped, so the list stays valid if `pred` or drop panics
+                if pred(&(*node.as_ptr()).value) {
+                    match prev {
+                        Some(prev) => (*prev.as_ptr()).next = cur,
+                        None => self.head = cur,
+                    }
+                    if cur.is_none() {
+                        self.tail = prev;
+                    }
+                    self.size -= 1;
+                    removed += 1;
+                    Node::free(node);
+                } else {
+                    prev = Some(node);
+                }
+            }
+        }
+        removed
     }
 }
 
 impl<T: Debug> LinkedList<T> {
     pub fn print(&self) {
-        let mut node = self.head.as_ref();
-        while let Some(n) = node {
-            println!("{:?}", n.value);
-            node = n.next.as_ref();
+        for value in self.iter() {
+            println!("{:?}", value);
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    /// free nodes one by one, dropping nested boxes recursively overflows stack for long list
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut ls = LinkedList::new();
+        ls.extend(iter);
+        ls
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for ele in iter {
+            self.push_back(ele);
         }
     }
 }
 
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// iterator of elements from front to back
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    len: usize,
+    _mark: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.next = node.next;
+            self.len -= 1;
+            &node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { ..*self }
+    }
+}
+
+/// iterator of mutable elements from front to back
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    len: usize,
+    _mark: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.next = node.next;
+            self.len -= 1;
+            &mut node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// iterator which moves elements out of list from front to back
+pub struct IntoIter<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.size, Some(self.list.size))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// cursor for editing list in place, each edit around the cursor takes O(1)
+///
+/// the cursor points at one element, or at the "ghost" position past the last element,
+/// moving next from the ghost goes back to the first element
+/// ```
+/// use algorithms_rs::lists::linked::LinkedList;
+/// let mut ls: LinkedList<i32> = (0..5).collect();
+/// let mut cursor = ls.cursor_front_mut();
+/// cursor.move_next();
+/// assert_eq!(cursor.current(), Some(&mut 1));
+/// assert_eq!(cursor.remove_current(), Some(1));
+/// cursor.insert_after(10);
+/// assert_eq!(cursor.peek_next(), Some(&mut 10));
+/// cursor.splice_after((20..22).collect());
+/// assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 2, 20, 21, 10, 3, 4]);
+/// ```
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    prev: Link<T>,
+    current: Link<T>,
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// return index of current element, `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// return current element, `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// return the element after current one, the first element at the ghost position
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        };
+        next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// move to the next element, or the ghost position after the last element
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.prev = Some(node);
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.prev = None;
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// insert element after current one, at the front of list at the ghost position
+    pub fn insert_after(&mut self, ele: T) {
+        match self.current {
+            Some(node) => unsafe {
+                let new = Node::alloc(ele, (*node.as_ptr()).next);
+                (*node.as_ptr()).next = Some(new);
+                if self.list.tail == Some(node) {
+                    self.list.tail = Some(new);
+                }
+                self.list.size += 1;
+            },
+            None => self.list.push_front(ele),
+        }
+    }
+
+    /// remove current element and move to the next one, do nothing at the ghost position
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        unsafe {
+            let next = (*node.as_ptr()).next;
+            match self.prev {
+                Some(prev) => (*prev.as_ptr()).next = next,
+                None => self.list.head = next,
+            }
+            if next.is_none() {
+                self.list.tail = self.prev;
+            }
+            self.current = next;
+            self.list.size -= 1;
+            Some(Node::free(node))
+        }
+    }
+
+    /// move all elements of `other` after current one, at the front of list at the ghost position
+    /// 时间复杂度: O(1)
+    pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            _ => return,
+        };
+        unsafe {
+            match self.current {
+                Some(node) => {
+                    (*tail.as_ptr()).next = (*node.as_ptr()).next;
+                    (*node.as_ptr()).next = Some(head);
+                    if self.list.tail == Some(node) {
+                        self.list.tail = Some(tail);
+                    }
+                }
+                None => {
+                    (*tail.as_ptr()).next = self.list.head;
+                    self.list.head = Some(head);
+                    if self.list.tail.is_none() {
+                        self.list.tail = Some(tail);
+                    }
+                }
+            }
+        }
+        self.list.size += other.size;
+        other.size = 0;
+    }
+}
 
 #[test]
 fn test() {
@@ -95,4 +605,138 @@ fn test() {
 
     ls.pop();
     ls.print()
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_linked_api() {
+    let mut ls = LinkedList::new();
+    assert_eq!(ls.pop_front(), None);
+    assert_eq!(ls.pop_back(), None);
+    ls.push_back(1);
+    assert_eq!(ls.peek_front(), ls.peek_back());
+    assert_eq!(ls.pop_back(), Some(1));
+    assert_eq!(ls.peek_back(), None);
+    // tail must be reset, otherwise push_back writes to freed node
+    ls.push_back(2);
+    ls.push_front(1);
+    ls.push_back(3);
+    assert_eq!(ls.pop(), Some(3));
+    ls.push_back(4);
+    assert_eq!(format!("{:?}", ls), "[1, 2, 4]");
+    *ls.peek_back_mut().unwrap() = 3;
+    *ls.peek_front_mut().unwrap() = 0;
+    ls.iter_mut().for_each(|x| *x *= 10);
+    assert_eq!(ls.iter().len(), 3);
+    assert_eq!(ls, (0..4).filter(|x| *x != 1).map(|x| x * 10).collect());
+    assert!(ls.contains(&20));
+    assert!(!ls.contains(&10));
+
+    let mut ls: LinkedList<i32> = (0..10).collect();
+    assert_eq!(ls.remove_if(|x| *x == 0 || *x == 9 || *x % 2 == 1), 6);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+    ls.push_back(10);
+    assert_eq!(ls.peek_back(), Some(&10));
+
+    let mut tail = ls.split_off(5).unwrap();
+    assert!(tail.is_empty());
+    tail = ls.split_off(2).unwrap();
+    ls.push_back(5);
+    tail.push_back(12);
+    ls.reverse();
+    ls.push_back(0);
+    tail.append(&mut LinkedList::new());
+    ls.append(&mut tail);
+    assert!(tail.is_empty());
+    tail.push_back(1);
+    assert_eq!(ls.len(), 8);
+    assert_eq!(ls.clone().into_iter().collect::<Vec<_>>(), vec![5, 4, 2, 0, 6, 8, 10, 12]);
+    let all = ls.split_off(0).unwrap();
+    assert!(ls.is_empty());
+    assert_eq!(all.len(), 8);
+    assert_eq!(all.peek_back(), Some(&12));
+
+    let mut empty: LinkedList<i32> = LinkedList::new();
+    empty.reverse();
+    assert_eq!(empty.split_off(0).unwrap().len(), 0);
+}
+
+/// no element is leaked or dropped twice, even if `remove_if` panics
+#[test]
+fn test_linked_drop() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut ls: LinkedList<Rc<()>> = (0..6).map(|_| counter.clone()).collect();
+    let mut calls = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        ls.remove_if(|_| {
+            calls += 1;
+            assert!(calls < 4);
+            calls % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(ls.len(), 5);
+    assert_eq!(Rc::strong_count(&counter), 6);
+    let mut iter = ls.into_iter();
+    iter.next();
+    drop(iter);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    // long list doesn't overflow stack
+    let ls: LinkedList<u32> = (0..1_000_000).collect();
+    drop(ls);
+}
+
+#[test]
+fn test_cursor() {
+    let mut ls: LinkedList<i32> = LinkedList::new();
+    let mut cursor = ls.cursor_front_mut();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.splice_after(LinkedList::new());
+    cursor.insert_after(1);
+    cursor.splice_after((-1..1).collect());
+    cursor.move_next();
+    assert_eq!(cursor.index(), Some(0));
+    assert_eq!(cursor.current(), Some(&mut -1));
+
+    // walk to the last element, insert after it and check tail is updated
+    while cursor.peek_next().is_some() {
+        cursor.move_next();
+    }
+    assert_eq!(cursor.index(), Some(2));
+    cursor.insert_after(2);
+    cursor.move_next();
+    cursor.splice_after((3..5).collect());
+    assert_eq!(ls.peek_back(), Some(&4));
+    ls.push_back(5);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4, 5]);
+
+    // remove every odd number, including the first and the last
+    let mut cursor = ls.cursor_front_mut();
+    while cursor.index().is_some() {
+        if cursor.current().is_some_and(|x| *x % 2 != 0) {
+            cursor.remove_current();
+        } else {
+            cursor.move_next();
+        }
+    }
+    // ghost position goes back to the front
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 0));
+    assert_eq!(ls.len(), 3);
+    assert_eq!(ls.peek_back(), Some(&4));
+    ls.push_back(6);
+    assert_eq!(ls.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+
+    let mut cursor = ls.cursor_front_mut();
+    for _ in 0..4 {
+        cursor.remove_current();
+    }
+    assert!(ls.is_empty());
+    assert_eq!(ls.peek_back(), None);
+    ls.push_back(1);
+    assert_eq!(ls.peek_front(), Some(&1));
+}
@@ -2,9 +2,12 @@
 // Create by VenmoSnake 2020/7/13 17:53
 //
 
+pub mod allocator;
 pub mod array;
 pub mod stack;
 pub mod queue;
 pub mod linked;
 pub mod double_linked;
+pub mod arena;
+pub mod intrusive;
 pub mod priority;
\ No newline at end of file
@@ -1,3 +1,96 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use crate::lists::array::Array;
+use crate::sorting::heap;
+
+/// priority queue, the greatest element is popped first
+/// the elements are kept as a max heap in `Array` by the heap primitives of `sorting::heap`,
+/// wrap elements in `std::cmp::Reverse` to pop the least one first
+/// 时间复杂度:
+///     push: O(logn)
+///     pop: O(logn)
+///     peek: O(1)
+/// ```
+/// use algorithms_rs::lists::priority::PriorityQueue;
+///
+/// let mut queue = PriorityQueue::new();
+/// queue.push(3);
+/// queue.push(8);
+/// queue.push(5);
+/// assert_eq!(queue.peek(), Some(&8));
+/// assert_eq!(queue.pop(), Some(8));
+/// assert_eq!(queue.pop(), Some(5));
+/// assert_eq!(queue.pop(), Some(3));
+/// assert_eq!(queue.pop(), None);
+/// ```
+pub struct PriorityQueue<T> {
+    heap: Array<T>,
+}
+
+impl<T: Ord> PriorityQueue<T> {
+    /// create empty priority queue
+    pub fn new() -> Self {
+        Self {
+            heap: Array::new(),
+        }
+    }
+
+    /// create empty priority queue with given capacity
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            heap: Array::with_capacity(cap),
+        }
+    }
+
+    /// return the number of elements
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// return the greatest element
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.get(0)
+    }
+
+    /// push element into queue
+    pub fn push(&mut self, ele: T) {
+        self.heap.append(ele);
+        heap::push_heap(&mut self.heap);
+    }
+
+    /// remove the greatest element and return it
+    pub fn pop(&mut self) -> Option<T> {
+        heap::pop_heap(&mut self.heap);
+        self.heap.pop()
+    }
+}
+
+impl<T: Ord> Default for PriorityQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test() {
+    use std::cmp::Reverse;
+
+    let mut queue = PriorityQueue::new();
+    for ele in [5, 1, 8, 3, 9, 2, 8].iter() {
+        queue.push(Reverse(*ele));
+    }
+    assert_eq!(queue.len(), 7);
+    assert_eq!(queue.peek(), Some(&Reverse(1)));
+    let mut popped = Vec::new();
+    while let Some(Reverse(ele)) = queue.pop() {
+        popped.push(ele);
+    }
+    assert_eq!(popped, vec![1, 2, 3, 5, 8, 8, 9]);
+    assert!(queue.is_empty());
+}
@@ -1,3 +1,455 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error;
+use crate::lists::allocator::Allocator;
+use crate::lists::array::Array;
+use crate::lists::linked::LinkedList;
+
+/// last in first out container
+/// ```
+/// use algorithms_rs::lists::array::Array;
+/// use algorithms_rs::lists::linked::LinkedList;
+/// use algorithms_rs::lists::stack::Stack;
+///
+/// fn drain<S: Stack<i32>>(mut stack: S) -> Vec<i32> {
+///     (1..4).for_each(|x| stack.push(x));
+///     let mut out = Vec::new();
+///     while let Some(x) = Stack::pop(&mut stack) {
+///         out.push(x);
+///     }
+///     out
+/// }
+/// assert_eq!(drain(Array::new()), vec![3, 2, 1]);
+/// assert_eq!(drain(LinkedList::new()), vec![3, 2, 1]);
+/// ```
+pub trait Stack<T> {
+    /// put element on the top
+    fn push(&mut self, ele: T);
+
+    /// take element from the top
+    fn pop(&mut self) -> Option<T>;
+
+    /// return element on the top
+    fn peek(&self) -> Option<&T>;
+
+    /// return number of elements
+    fn len(&self) -> usize;
+
+    /// return true if there is no element
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// the top is the last element
+/// 时间复杂度: O(1), push is amortized
+impl<T, A: Allocator> Stack<T> for Array<T, A> {
+    fn push(&mut self, ele: T) {
+        self.append(ele)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        Array::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+
+    fn len(&self) -> usize {
+        Array::len(self)
+    }
+}
+
+/// the top is the first element, as only the front of singly linked list is O(1) to remove,
+/// note that the inherent `LinkedList::pop` takes the last element, call `Stack::pop` instead
+/// 时间复杂度: O(1)
+impl<T> Stack<T> for LinkedList<T> {
+    fn push(&mut self, ele: T) {
+        self.push_front(ele)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_front()
+    }
+
+    fn len(&self) -> usize {
+        LinkedList::len(self)
+    }
+}
+
+/// return true if every bracket of `()`, `[]` and `{}` in `s` is closed by the same kind
+/// in the right order, other characters are ignored
+/// 时间复杂度: O(n)
+/// ```
+/// use algorithms_rs::lists::stack::is_balanced;
+/// assert!(is_balanced("{a[b(c)d]}()"));
+/// assert!(!is_balanced("([)]"));
+/// assert!(!is_balanced("(("));
+/// ```
+pub fn is_balanced(s: &str) -> bool {
+    let mut stack: Array<char> = Array::new();
+    for c in s.chars() {
+        match c {
+            '(' => stack.push(')'),
+            '[' => stack.push(']'),
+            '{' => stack.push('}'),
+            ')' | ']' | '}' if Stack::pop(&mut stack) != Some(c) => return false,
+            _ => {}
+        }
+    }
+    stack.is_empty()
+}
+
+/// token of expression in postfix order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    Num(i64),
+    /// one of `+ - * / % ^`
+    Op(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "{}", n),
+            Token::Op(op) => write!(f, "{}", op),
+        }
+    }
+}
+
+/// return precedence and whether operator is right associative, `None` if it's not an operator
+fn precedence(op: char) -> Option<(u8, bool)> {
+    match op {
+        '+' | '-' => Some((1, false)),
+        '*' | '/' | '%' => Some((2, false)),
+        '^' => Some((3, true)),
+        _ => None,
+    }
+}
+
+/// convert infix expression to postfix by shunting-yard algorithm
+///
+/// the expression has non negative integers, operators `+ - * / % ^` and parentheses,
+/// `^` is right associative and binds tighter than the others, there is no unary minus
+/// 时间复杂度: O(n)
+/// ```
+/// use algorithms_rs::lists::stack::to_postfix;
+///
+/// let postfix = to_postfix("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3").unwrap();
+/// let postfix: Vec<String> = postfix.iter().map(|t| t.to_string()).collect();
+/// assert_eq!(postfix.join(" "), "3 4 2 * 1 5 - 2 3 ^ ^ / +");
+/// assert!(to_postfix("(1 + 2").is_err());
+/// ```
+pub fn to_postfix(expr: &str) -> Result<Vec<Token>, error::Error> {
+    let mut out = Vec::new();
+    // operators and '(' waiting for their operands
+    let mut ops: Array<char> = Array::new();
+    // an operand or '(' is expected next, otherwise an operator or ')'
+    let mut operand = true;
+    let mut chars = expr.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '0'..='9' if operand => {
+                let mut n = c.to_digit(10).unwrap() as i64;
+                while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    n = n.checked_mul(10)
+                        .and_then(|n| n.checked_add(d as i64))
+                        .ok_or(error::Error::ArithmeticOverflow)?;
+                    chars.next();
+                }
+                out.push(Token::Num(n));
+                operand = false;
+            }
+            '(' if operand => ops.push('('),
+            ')' if !operand => loop {
+                match Stack::pop(&mut ops) {
+                    Some('(') => break,
+                    Some(op) => out.push(Token::Op(op)),
+                    None => return Err(error::Error::InvalidExpression),
+                }
+            },
+            _ if !operand => {
+                let (prec, right) = precedence(c).ok_or(error::Error::InvalidExpression)?;
+                while let Some(&top) = ops.peek() {
+                    match precedence(top) {
+                        Some((top_prec, _)) if top_prec > prec || (top_prec == prec && !right) => {
+                            out.push(Token::Op(top));
+                            Stack::pop(&mut ops);
+                        }
+                        _ => break,
+                    }
+                }
+                ops.push(c);
+                operand = true;
+            }
+            _ => return Err(error::Error::InvalidExpression),
+        }
+    }
+    if operand {
+        return Err(error::Error::InvalidExpression);
+    }
+    while let Some(op) = Stack::pop(&mut ops) {
+        if op == '(' {
+            return Err(error::Error::InvalidExpression);
+        }
+        out.push(Token::Op(op));
+    }
+    Ok(out)
+}
+
+/// apply operator, the division is truncated toward zero like `/` and `%` of rust
+fn apply(op: char, a: i64, b: i64) -> Result<i64, error::Error> {
+    if (op == '/' || op == '%') && b == 0 {
+        return Err(error::Error::DivideByZero);
+    }
+    let res = match op {
+        '+' => a.checked_add(b),
+        '-' => a.checked_sub(b),
+        '*' => a.checked_mul(b),
+        '/' => a.checked_div(b),
+        '%' => a.checked_rem(b),
+        '^' => {
+            if b < 0 {
+                return Err(error::Error::InvalidExpression);
+            }
+            u32::try_from(b).ok().and_then(|exp| a.checked_pow(exp))
+        }
+        _ => return Err(error::Error::InvalidExpression),
+    };
+    res.ok_or(error::Error::ArithmeticOverflow)
+}
+
+/// evaluate postfix expression
+/// 时间复杂度: O(n)
+/// ```
+/// use algorithms_rs::lists::stack::{eval_postfix, Token};
+///
+/// let tokens = [Token::Num(2), Token::Num(3), Token::Num(4), Token::Op('*'), Token::Op('+')];
+/// assert_eq!(eval_postfix(&tokens).unwrap(), 14);
+/// assert!(eval_postfix(&tokens[1..]).is_err());
+/// ```
+pub fn eval_postfix(tokens: &[Token]) -> Result<i64, error::Error> {
+    let mut stack: Array<i64> = Array::new();
+    for token in tokens {
+        match *token {
+            Token::Num(n) => stack.push(n),
+            Token::Op(op) => {
+                let b = Stack::pop(&mut stack).ok_or(error::Error::InvalidExpression)?;
+                let a = Stack::pop(&mut stack).ok_or(error::Error::InvalidExpression)?;
+                stack.push(apply(op, a, b)?);
+            }
+        }
+    }
+    match (Stack::pop(&mut stack), stack.is_empty()) {
+        (Some(res), true) => Ok(res),
+        _ => Err(error::Error::InvalidExpression),
+    }
+}
+
+/// evaluate infix expression, see `to_postfix` for the syntax
+/// ```
+/// use algorithms_rs::lists::stack::eval;
+/// assert_eq!(eval("(1 + 2) * 3 - 2 ^ 3").unwrap(), 1);
+/// assert!(eval("1 / (2 - 2)").is_err());
+/// ```
+pub fn eval(expr: &str) -> Result<i64, error::Error> {
+    eval_postfix(&to_postfix(expr)?)
+}
+
+/// return index of the first greater element on the right of each element by monotonic stack,
+/// `None` if there is no such element, element not comparable with itself like NaN is never greater
+/// and has no greater element
+/// 时间复杂度: O(n)
+/// ```
+/// use algorithms_rs::lists::stack::next_greater;
+/// assert_eq!(next_greater(&[2, 1, 2, 4, 3]), vec![Some(3), Some(2), Some(3), None, None]);
+/// ```
+pub fn next_greater<T: PartialOrd>(arr: &[T]) -> Vec<Option<usize>> {
+    let mut res = vec![None; arr.len()];
+    // indices waiting for their answer, their elements are not increasing from bottom to top
+    let mut stack: Array<usize> = Array::new();
+    for (i, ele) in arr.iter().enumerate() {
+        // NaN would keep the elements under it from being popped
+        if ele.partial_cmp(ele).is_none() {
+            continue;
+        }
+        while let Some(&top) = stack.peek() {
+            if arr[top].partial_cmp(ele) != Some(Ordering::Less) {
+                break;
+            }
+            res[top] = Some(i);
+            Stack::pop(&mut stack);
+        }
+        stack.push(i);
+    }
+    res
+}
+
+/// stack which returns its minimum element in O(1)
+/// ```
+/// use algorithms_rs::lists::stack::{MinStack, Stack};
+///
+/// let mut stack = MinStack::new();
+/// stack.push(3);
+/// stack.push(1);
+/// stack.push(2);
+/// assert_eq!(stack.min(), Some(&1));
+/// stack.pop();
+/// stack.pop();
+/// assert_eq!(stack.min(), Some(&3));
+/// ```
+pub struct MinStack<T> {
+    values: Array<T>,
+    /// indices of minimum values, the top is the minimum of whole stack
+    mins: Array<usize>,
+}
+
+impl<T: Ord> MinStack<T> {
+    pub fn new() -> Self {
+        Self {
+            values: Array::new(),
+            mins: Array::new(),
+        }
+    }
+
+    /// return the minimum element
+    /// 时间复杂度: O(1)
+    pub fn min(&self) -> Option<&T> {
+        self.mins.peek().map(|&i| &self.values[i])
+    }
+}
+
+impl<T: Ord> Default for MinStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 时间复杂度: O(1), push is amortized
+impl<T: Ord> Stack<T> for MinStack<T> {
+    fn push(&mut self, ele: T) {
+        // an equal element doesn't become minimum, the older one stays longer
+        if self.min().is_none_or(|min| ele < *min) {
+            self.mins.push(self.values.len());
+        }
+        self.values.push(ele);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let ele = Stack::pop(&mut self.values)?;
+        if self.mins.peek() == Some(&self.values.len()) {
+            Stack::pop(&mut self.mins);
+        }
+        Some(ele)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.values.peek()
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+#[cfg(test)]
+fn check_stack<S: Stack<i32> + Default>() {
+    let mut stack = S::default();
+    assert!(stack.is_empty());
+    assert_eq!(stack.pop(), None);
+    assert_eq!(stack.peek(), None);
+    for x in 0..100 {
+        stack.push(x);
+    }
+    assert_eq!(stack.len(), 100);
+    assert_eq!(stack.peek(), Some(&99));
+    for x in (50..100).rev() {
+        assert_eq!(stack.pop(), Some(x));
+    }
+    stack.push(-1);
+    assert_eq!(stack.pop(), Some(-1));
+    assert_eq!(stack.peek(), Some(&49));
+    assert_eq!(stack.len(), 50);
+}
+
+#[test]
+fn test_stack() {
+    check_stack::<Array<i32>>();
+    check_stack::<LinkedList<i32>>();
+    check_stack::<MinStack<i32>>();
+}
+
+#[test]
+fn test_balanced() {
+    assert!(is_balanced(""));
+    assert!(is_balanced("fn main() { let a = [1, 2]; }"));
+    assert!(!is_balanced(")("));
+    assert!(!is_balanced("{[}]"));
+    assert!(!is_balanced("[]]"));
+}
+
+#[test]
+fn test_expression() {
+    let postfix = |expr: &str| {
+        to_postfix(expr).map(|tokens| tokens.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(" "))
+    };
+    assert_eq!(postfix("1 - 2 - 3").unwrap(), "1 2 - 3 -");
+    assert_eq!(postfix("2 ^ 3 ^ 2").unwrap(), "2 3 2 ^ ^");
+    assert_eq!(postfix("((10))*(2+3)%4").unwrap(), "10 2 3 + * 4 %");
+    for bad in ["", "1 +", "+ 1", "1 2", "()", "(1))", "1 & 2", "-1", "(1 + 2"] {
+        assert!(to_postfix(bad).is_err(), "{}", bad);
+    }
+    assert_eq!(eval("1 - 2 - 3").unwrap(), -4);
+    assert_eq!(eval("2 ^ 3 ^ 2").unwrap(), 512);
+    assert_eq!(eval("7 / 2 * 2 + 7 % 2").unwrap(), 7);
+    assert_eq!(eval("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3").unwrap(), 3);
+    assert!(matches!(eval("1 % 0"), Err(error::Error::DivideByZero)));
+    assert!(matches!(eval("2 ^ 64"), Err(error::Error::ArithmeticOverflow)));
+    assert!(matches!(eval("99999999999999999999"), Err(error::Error::ArithmeticOverflow)));
+    assert!(matches!(eval("2 ^ (0 - 1)"), Err(error::Error::InvalidExpression)));
+    assert!(eval_postfix(&[]).is_err());
+    assert!(eval_postfix(&[Token::Num(1), Token::Num(2)]).is_err());
+    assert!(eval_postfix(&[Token::Num(1), Token::Num(2), Token::Op('?')]).is_err());
+}
+
+#[test]
+fn test_next_greater() {
+    assert_eq!(next_greater::<i32>(&[]), vec![]);
+    assert_eq!(next_greater(&[1, 1, 1]), vec![None, None, None]);
+    assert_eq!(next_greater(&[5, 4, 3, 6]), vec![Some(3), Some(3), Some(3), None]);
+    let arr = [3, 7, 1, 7, 8, 4, 5, 2, 6, 0];
+    let brute: Vec<Option<usize>> = (0..arr.len())
+        .map(|i| (i + 1..arr.len()).find(|&j| arr[j] > arr[i]))
+        .collect();
+    assert_eq!(next_greater(&arr), brute);
+    assert_eq!(next_greater(&[1.0, f64::NAN]), vec![None, None]);
+    assert_eq!(next_greater(&[1.0, f64::NAN, 2.0]), vec![Some(2), None, None]);
+    assert_eq!(next_greater(&[f64::NAN, 1.0]), vec![None, None]);
+}
+
+#[test]
+fn test_min_stack() {
+    let mut stack = MinStack::new();
+    assert_eq!(stack.min(), None);
+    let values = [5, 3, 3, 4, 1, 2, 1, 6];
+    for (i, &x) in values.iter().enumerate() {
+        stack.push(x);
+        assert_eq!(stack.min(), values[..=i].iter().min());
+    }
+    for i in (0..values.len()).rev() {
+        assert_eq!(stack.min(), values[..=i].iter().min());
+        assert_eq!(stack.pop(), Some(values[i]));
+    }
+    assert_eq!(stack.min(), None);
+}
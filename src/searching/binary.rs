@@ -1,3 +1,272 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 11:41
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// return element index if find in sorted `data_set` otherwise return `None`,
+/// any index is returned when there are several equal elements
+/// 时间复杂度: O(logn)
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = vec![4, 21, 22, 31, 55, 67];
+/// assert_eq!(binary::search(&data_set, &31), Some(3));
+/// assert_eq!(binary::search(&data_set, &666), None);
+///
+/// let data_set = "abcdef";
+/// assert_eq!(binary::search(&data_set, &b'c'), Some(2));
+/// ```
+pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    binary_search_by(data_set, |x| x.cmp(ele)).ok()
+}
+
+/// binary search sorted `data_set` with a function returning the ordering of element against target,
+/// return `Ok(index)` of a matched element, otherwise `Err(index)` where the target could be inserted
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [(1, 'a'), (3, 'b'), (5, 'c')];
+/// assert_eq!(binary::binary_search_by(&data_set, |x| x.0.cmp(&3)), Ok(1));
+/// assert_eq!(binary::binary_search_by(&data_set, |x| x.0.cmp(&4)), Err(2));
+/// ```
+pub fn binary_search_by<T, E, F>(data_set: &T, mut f: F) -> Result<usize, usize>
+    where T: AsRef<[E]>,
+          F: FnMut(&E) -> Ordering
+{
+    let data_set = data_set.as_ref();
+    let (mut lo, mut hi) = (0, data_set.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match f(&data_set[mid]) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+    Err(lo)
+}
+
+/// `data_set` is partitioned by `pred`: elements matching it are all before the others,
+/// return the index of first element not matching it
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [1, 2, 3, 3, 5, 6, 7];
+/// assert_eq!(binary::partition_point(&data_set, |x| *x < 5), 4);
+/// ```
+pub fn partition_point<T, E, P>(data_set: &T, mut pred: P) -> usize
+    where T: AsRef<[E]>,
+          P: FnMut(&E) -> bool
+{
+    let data_set = data_set.as_ref();
+    let (mut lo, mut hi) = (0, data_set.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&data_set[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// return the index of first element not less than `ele` in sorted `data_set`
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [1, 2, 4, 4, 4, 7];
+/// assert_eq!(binary::lower_bound(&data_set, &4), 2);
+/// assert_eq!(binary::lower_bound(&data_set, &5), 5);
+/// ```
+pub fn lower_bound<'a, T, E>(data_set: &'a T, ele: &'a E) -> usize
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    partition_point(data_set, |x| x < ele)
+}
+
+/// return the index of first element greater than `ele` in sorted `data_set`
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [1, 2, 4, 4, 4, 7];
+/// assert_eq!(binary::upper_bound(&data_set, &4), 5);
+/// assert_eq!(binary::upper_bound(&data_set, &0), 0);
+/// ```
+pub fn upper_bound<'a, T, E>(data_set: &'a T, ele: &'a E) -> usize
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    partition_point(data_set, |x| x <= ele)
+}
+
+/// return the range of elements equal to `ele` in sorted `data_set`, it's empty if not found
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [1, 2, 4, 4, 4, 7];
+/// assert_eq!(binary::equal_range(&data_set, &4), 2..5);
+/// assert_eq!(binary::equal_range(&data_set, &3), 2..2);
+/// ```
+pub fn equal_range<'a, T, E>(data_set: &'a T, ele: &'a E) -> Range<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    // narrow down to an equal element first, then search both bounds around it
+    match binary_search_by(&data_set, |x| x.cmp(ele)) {
+        Ok(mid) => {
+            let start = lower_bound(&&data_set[..mid], ele);
+            let end = mid + upper_bound(&&data_set[mid..], ele);
+            start..end
+        }
+        Err(index) => index..index,
+    }
+}
+
+/// branchless lower bound, the range always shrinks by half whatever the comparison is,
+/// so the loop has fixed trip count and the comparison compiles to conditional move
+/// instead of a branch which is mispredicted half of the time
+/// 时间复杂度: O(logn)
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = [1, 2, 4, 4, 4, 7];
+/// assert_eq!(binary::branchless_lower_bound(&data_set, &4), 2);
+/// assert_eq!(binary::branchless_lower_bound(&data_set, &8), 6);
+/// ```
+pub fn branchless_lower_bound<'a, T, E>(data_set: &'a T, ele: &'a E) -> usize
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    if data_set.is_empty() {
+        return 0;
+    }
+    // the answer is in [base, base + size]
+    let mut base = 0;
+    let mut size = data_set.len();
+    while size > 1 {
+        let half = size / 2;
+        base = if data_set[base + half] < *ele { base + half } else { base };
+        size -= half;
+    }
+    base + (data_set[base] < *ele) as usize
+}
+
+/// branchless binary search, return element index if find in sorted `data_set` otherwise return `None`,
+/// the first one is returned when there are several equal elements
+///```
+/// use algorithms_rs::searching::binary;
+///
+/// let data_set = vec![4, 21, 22, 31, 55, 67];
+/// assert_eq!(binary::branchless_search(&data_set, &31), Some(3));
+/// assert_eq!(binary::branchless_search(&data_set, &666), None);
+/// ```
+pub fn branchless_search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let index = branchless_lower_bound(data_set, ele);
+    match data_set.as_ref().get(index) {
+        Some(x) if x == ele => Some(index),
+        _ => None,
+    }
+}
+
+/// sorted elements in Eytzinger (BFS) order: the root is at 0 and the children of `k`
+/// are at `2k + 1` and `2k + 2`, like a binary heap
+/// the first levels searched by every lookup are packed at the front and share cache lines,
+/// and the next probe doesn't depend on a branch, so it beats binary search on large arrays
+/// 时间复杂度: O(logn)
+/// 空间复杂度: O(n)
+///```
+/// use algorithms_rs::searching::binary::Eytzinger;
+///
+/// let layout = Eytzinger::new(&[1, 3, 5, 7, 9, 11]);
+/// assert_eq!(layout.as_slice(), &[7, 3, 11, 1, 5, 9]);
+///
+/// let index = layout.lower_bound(&4).unwrap();
+/// assert_eq!(layout.get(index), Some(&5));
+/// assert_eq!(layout.lower_bound(&12), None);
+/// assert_eq!(layout.search(&9).map(|index| layout[index]), Some(9));
+/// ```
+pub struct Eytzinger<E> {
+    tree: Vec<E>,
+}
+
+impl<E: Ord + Clone> Eytzinger<E> {
+    /// build layout from sorted slice
+    /// 时间复杂度: O(n)
+    pub fn new(sorted: &[E]) -> Self {
+        let mut order = vec![0; sorted.len()];
+        let mut next = 0;
+        Self::fill(&mut order, 0, &mut next);
+        Self {
+            tree: order.into_iter().map(|index| sorted[index].clone()).collect(),
+        }
+    }
+
+    /// assign sorted indexes to the subtree rooted at `k` by in-order traversal
+    fn fill(order: &mut [usize], k: usize, next: &mut usize) {
+        if k < order.len() {
+            Self::fill(order, 2 * k + 1, next);
+            order[k] = *next;
+            *next += 1;
+            Self::fill(order, 2 * k + 2, next);
+        }
+    }
+}
+
+impl<E: Ord> Eytzinger<E> {
+    /// return the layout index of the least element not less than `ele`, `None` if all are less
+    pub fn lower_bound(&self, ele: &E) -> Option<usize> {
+        // 1-based index, so the path is the bits of `k`
+        let mut k = 1;
+        while k <= self.tree.len() {
+            k = 2 * k + (self.tree[k - 1] < *ele) as usize;
+        }
+        // the answer is where the path turned left last time:
+        // drop the trailing right turns and that left turn
+        k = k.checked_shr(k.trailing_ones() + 1).unwrap_or(0);
+        k.checked_sub(1)
+    }
+
+    /// return the layout index of element equal to `ele`, `None` if not found
+    pub fn search(&self, ele: &E) -> Option<usize> {
+        self.lower_bound(ele).filter(|index| self.tree[*index] == *ele)
+    }
+
+    /// return element at layout index
+    pub fn get(&self, index: usize) -> Option<&E> {
+        self.tree.get(index)
+    }
+
+    /// return elements in layout order
+    pub fn as_slice(&self) -> &[E] {
+        &self.tree
+    }
+
+    /// return the number of elements
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// return true if there is no element
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+}
+
+impl<E> std::ops::Index<usize> for Eytzinger<E> {
+    type Output = E;
+
+    fn index(&self, index: usize) -> &E {
+        &self.tree[index]
+    }
+}
@@ -0,0 +1,70 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/17 10:12
+//
+use std::cmp::Ordering;
+
+/// exponential (galloping) search, probe index 1, 2, 4, 8... until passing `ele`
+/// then binary search between the last two probes,
+/// return element index if find in sorted `data_set` otherwise return `None`
+/// 时间复杂度: O(logi), i为元素所在位置
+///```
+/// use algorithms_rs::searching::exponential;
+///
+/// let data_set = vec![4, 21, 22, 31, 55, 67];
+/// assert_eq!(exponential::search(&data_set, &31), Some(3));
+/// assert_eq!(exponential::search(&data_set, &666), None);
+///
+/// let data_set = "abcdef";
+/// assert_eq!(exponential::search(&data_set, &b'c'), Some(2));
+/// ```
+pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    search_unbounded(|index| data_set.get(index), &ele)
+}
+
+/// exponential search over sorted source of unknown length, `get` returns the element at index
+/// or `None` past the end, it's only called at O(logi) indexes so the source can be lazily computed
+///```
+/// use algorithms_rs::searching::exponential;
+///
+/// // squares without an upper bound
+/// assert_eq!(exponential::search_unbounded(|i| Some(i * i), &1369), Some(37));
+/// assert_eq!(exponential::search_unbounded(|i| Some(i * i), &1370), None);
+/// // a source ending at 100
+/// assert_eq!(exponential::search_unbounded(|i| if i < 100 { Some(i * 2) } else { None }, &300), None);
+/// ```
+pub fn search_unbounded<E, F>(mut get: F, ele: &E) -> Option<usize>
+    where E: Ord,
+          F: FnMut(usize) -> Option<E>
+{
+    // the index past the end compares greater than everything
+    let mut compare = |index| get(index).map_or(Ordering::Greater, |x| x.cmp(ele));
+    // probe index 0, 1, 3, 7..., saturated at `usize::MAX` so the last range is searched too
+    let mut probe = 0usize;
+    let mut lo = 0;
+    loop {
+        match compare(probe) {
+            Ordering::Less if probe == usize::MAX => return None,
+            Ordering::Less => {
+                lo = probe + 1;
+                probe = probe.saturating_mul(2).saturating_add(1);
+            }
+            Ordering::Equal => return Some(probe),
+            Ordering::Greater => break,
+        }
+    }
+    // `ele` is in [lo, probe)
+    let mut hi = probe;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match compare(mid) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Some(mid),
+        }
+    }
+    None
+}
@@ -0,0 +1,56 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/17 11:30
+//
+use std::cmp::Ordering;
+
+/// fibonacci search, split the range by fibonacci numbers instead of halves,
+/// so only addition and subtraction are needed to find the next probe,
+/// return element index if find in sorted `data_set` otherwise return `None`
+/// 时间复杂度: O(logn)
+///```
+/// use algorithms_rs::searching::fibonacci;
+///
+/// let data_set = vec![4, 21, 22, 31, 55, 67];
+/// assert_eq!(fibonacci::search(&data_set, &31), Some(3));
+/// assert_eq!(fibonacci::search(&data_set, &666), None);
+///
+/// let data_set = "abcdef";
+/// assert_eq!(fibonacci::search(&data_set, &b'c'), Some(2));
+/// ```
+pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    let len = data_set.len();
+    // fib is the smallest fibonacci number not less than len, fib1 and fib2 are the two before it
+    let (mut fib2, mut fib1, mut fib) = (0, 1, 1);
+    while fib < len {
+        fib2 = fib1;
+        fib1 = fib;
+        fib = fib1 + fib2;
+    }
+    // elements before start are less than `ele`
+    let mut start = 0;
+    while fib > 1 {
+        let index = (start + fib2 - 1).min(len - 1);
+        match data_set[index].cmp(ele) {
+            Ordering::Less => {
+                fib = fib1;
+                fib1 = fib2;
+                fib2 = fib - fib1;
+                start = index + 1;
+            }
+            Ordering::Greater => {
+                fib = fib2;
+                fib1 -= fib2;
+                fib2 = fib - fib1;
+            }
+            Ordering::Equal => return Some(index),
+        }
+    }
+    if fib1 == 1 && start < len && data_set[start] == *ele {
+        return Some(start);
+    }
+    None
+}
@@ -0,0 +1,66 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/17 10:40
+//
+
+/// numeric key which can be interpolated, implemented for all primitive integers and floats
+pub trait InterpolationKey: PartialOrd + Copy {
+    /// the key as float for estimating its position
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_interpolation_key {
+    ($($t:ty)*) => {
+        $(impl InterpolationKey for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_interpolation_key!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+/// interpolation search, estimate the position of `ele` by its value between the two ends,
+/// return element index if find in sorted `data_set` otherwise return `None`
+/// 时间复杂度: 均匀分布时O(loglogn), 最坏O(n)
+///```
+/// use algorithms_rs::searching::interpolation;
+///
+/// let data_set = vec![10, 20, 30, 40, 50, 60, 70];
+/// assert_eq!(interpolation::search(&data_set, &40), Some(3));
+/// assert_eq!(interpolation::search(&data_set, &45), None);
+///
+/// let data_set = [0.5, 1.5, 2.5, 3.5];
+/// assert_eq!(interpolation::search(&data_set, &2.5), Some(2));
+/// ```
+pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: InterpolationKey,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    if data_set.is_empty() {
+        return None;
+    }
+    let (mut lo, mut hi) = (0, data_set.len() - 1);
+    while lo <= hi && *ele >= data_set[lo] && *ele <= data_set[hi] {
+        let (low, high) = (data_set[lo].to_f64(), data_set[hi].to_f64());
+        let pos = if high > low {
+            let estimate = (ele.to_f64() - low) / (high - low) * (hi - lo) as f64;
+            // rounding error of large keys must not leave the range
+            lo + (estimate as usize).min(hi - lo)
+        } else {
+            lo
+        };
+        if data_set[pos] < *ele {
+            lo = pos + 1;
+        } else if data_set[pos] > *ele {
+            if pos == 0 {
+                break;
+            }
+            hi = pos - 1;
+        } else {
+            return Some(pos);
+        }
+    }
+    None
+}
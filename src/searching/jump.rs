@@ -0,0 +1,35 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/17 11:05
+//
+
+/// jump search, jump forward by blocks of √n until the block end is not less than `ele`,
+/// then scan that block linearly,
+/// return element index if find in sorted `data_set` otherwise return `None`
+/// 时间复杂度: O(√n)
+///```
+/// use algorithms_rs::searching::jump;
+///
+/// let data_set = vec![4, 21, 22, 31, 55, 67];
+/// assert_eq!(jump::search(&data_set, &31), Some(3));
+/// assert_eq!(jump::search(&data_set, &666), None);
+///
+/// let data_set = "abcdef";
+/// assert_eq!(jump::search(&data_set, &b'c'), Some(2));
+/// ```
+pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Ord,
+          T: AsRef<[E]>
+{
+    let data_set = data_set.as_ref();
+    let len = data_set.len();
+    let step = ((len as f64).sqrt() as usize).max(1);
+    let mut start = 0;
+    while start < len && data_set[(start + step).min(len) - 1] < *ele {
+        start += step;
+    }
+    let block = &data_set[start.min(len)..(start + step).min(len)];
+    match block.iter().position(|x| x >= ele) {
+        Some(index) if block[index] == *ele => Some(start + index),
+        _ => None,
+    }
+}
@@ -4,6 +4,8 @@
 
 /// return element index if find in `data_set` otherwise return `None`
 ///```no_run
+/// use algorithms_rs::searching::liner;
+///
 /// let data_set = vec![21, 22, 31, 4, 55, 67];
 /// assert_eq!(liner::search(&data_set, &4), Some(3));
 /// assert_eq!(liner::search(&data_set, &666), None);
@@ -24,11 +26,170 @@ pub fn search<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
     where E: Eq + PartialEq,
           T: AsRef<[E]>
 {
-    let data_set = data_set.as_ref();
-    for index in 0..data_set.len() {
-        if &data_set[index] == ele {
-            return Some(index);
+    find_by(data_set, |x| x == ele)
+}
+
+/// return index of the first element matching `predicate` otherwise return `None`
+///```
+/// use algorithms_rs::searching::liner;
+///
+/// let data_set = vec![21, 22, 31, 4, 55, 67];
+/// assert_eq!(liner::find_by(&data_set, |x| x % 2 == 0), Some(1));
+/// assert_eq!(liner::find_by(&data_set, |x| *x > 100), None);
+/// ```
+pub fn find_by<T, E, P>(data_set: &T, predicate: P) -> Option<usize>
+    where T: AsRef<[E]>,
+          P: FnMut(&E) -> bool
+{
+    data_set.as_ref().iter().position(predicate)
+}
+
+/// return index of the last element equal to `ele` otherwise return `None`
+///```
+/// use algorithms_rs::searching::liner;
+///
+/// let data_set = "abcabc";
+/// assert_eq!(liner::find_last(&data_set, &b'b'), Some(4));
+/// assert_eq!(liner::find_last(&data_set, &b'h'), None);
+/// ```
+pub fn find_last<'a, T, E>(data_set: &'a T, ele: &'a E) -> Option<usize>
+    where E: Eq + PartialEq,
+          T: AsRef<[E]>
+{
+    find_last_by(data_set, |x| x == ele)
+}
+
+/// return index of the last element matching `predicate` otherwise return `None`
+pub fn find_last_by<T, E, P>(data_set: &T, predicate: P) -> Option<usize>
+    where T: AsRef<[E]>,
+          P: FnMut(&E) -> bool
+{
+    data_set.as_ref().iter().rposition(predicate)
+}
+
+/// return iterator of indexes of all elements equal to `ele` in ascending order
+///```
+/// use algorithms_rs::searching::liner;
+///
+/// let data_set = vec![4, 21, 4, 31, 4];
+/// assert_eq!(liner::find_all(&data_set, &4).collect::<Vec<_>>(), vec![0, 2, 4]);
+/// assert_eq!(liner::find_all(&data_set, &666).next(), None);
+/// ```
+pub fn find_all<'a, T, E>(data_set: &'a T, ele: &'a E) -> impl Iterator<Item=usize> + 'a
+    where E: Eq + PartialEq,
+          T: AsRef<[E]>
+{
+    data_set.as_ref()
+        .iter()
+        .enumerate()
+        .filter(move |(_, x)| *x == ele)
+        .map(|(index, _)| index)
+}
+
+/// return index of the first element equal to each of `needles` in one pass over `data_set`,
+/// the scan stops once all needles are found
+/// 时间复杂度: O(nm), m为needles的数量
+///```
+/// use algorithms_rs::searching::liner;
+///
+/// let data_set = "ERROR: disk full\nWARN: retry";
+/// assert_eq!(liner::search_many(&data_set, b":\nX"), vec![Some(5), Some(16), None]);
+/// ```
+pub fn search_many<'a, T, N, E>(data_set: &'a T, needles: &'a N) -> Vec<Option<usize>>
+    where E: Eq + PartialEq,
+          T: AsRef<[E]>,
+          N: AsRef<[E]> + ?Sized
+{
+    let needles = needles.as_ref();
+    let mut found = vec![None; needles.len()];
+    let mut remaining = needles.len();
+    for (index, x) in data_set.as_ref().iter().enumerate() {
+        if remaining == 0 {
+            break;
+        }
+        for (needle, position) in needles.iter().zip(found.iter_mut()) {
+            if position.is_none() && x == needle {
+                *position = Some(index);
+                remaining -= 1;
+            }
+        }
+    }
+    found
+}
+
+/// element whose equality can be trusted by `search_sentinel`,
+/// implemented for primitive integers, `char`, `bool` and `String`
+/// # Safety
+/// `eq` must be reflexive: a clone of value compares equal to the value. the sentinel scan
+/// reads without bounds check and relies on it to stop, a broken `eq` reads past the end
+pub unsafe trait SentinelKey: Eq + Clone {}
+
+macro_rules! impl_sentinel_key {
+    ($($t:ty)*) => {
+        $(unsafe impl SentinelKey for $t {})*
+    };
+}
+
+impl_sentinel_key!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize char bool String);
+
+/// linear search with `ele` written over the last element as sentinel,
+/// so the scan stops at the sentinel without comparing index with length, the last element
+/// is restored before returning even if comparison panics
+///```
+/// use algorithms_rs::searching::liner;
+///
+/// let mut data_set = vec![21, 22, 31, 4, 55, 67];
+/// assert_eq!(liner::search_sentinel(&mut data_set, &4), Some(3));
+/// assert_eq!(liner::search_sentinel(&mut data_set, &67), Some(5));
+/// assert_eq!(liner::search_sentinel(&mut data_set, &666), None);
+/// assert_eq!(data_set, vec![21, 22, 31, 4, 55, 67]);
+/// ```
+/// types without trusted equality are rejected, as the scan would never stop for them
+///```compile_fail
+/// use algorithms_rs::searching::liner;
+///
+/// #[derive(Clone)]
+/// struct Never;
+/// impl PartialEq for Never {
+///     fn eq(&self, _: &Self) -> bool { false }
+/// }
+/// impl Eq for Never {}
+///
+/// liner::search_sentinel(&mut [Never, Never], &Never);
+/// ```
+pub fn search_sentinel<E: SentinelKey>(data_set: &mut [E], ele: &E) -> Option<usize> {
+    let len = data_set.len();
+    if len == 0 {
+        return None;
+    }
+    let last = std::mem::replace(&mut data_set[len - 1], ele.clone());
+    let guard = Sentinel { data_set, last: Some(last) };
+    let mut index = 0;
+    // safety: `data_set[len - 1]` is a clone of `ele` and `SentinelKey` promises they are equal,
+    // so the scan stops at `len - 1` at the latest
+    while unsafe { guard.data_set.get_unchecked(index) } != ele {
+        index += 1;
+    }
+    drop(guard);
+    if index + 1 < len {
+        Some(index)
+    } else if data_set[len - 1] == *ele {
+        Some(len - 1)
+    } else {
+        None
+    }
+}
+
+/// put the last element back on drop
+struct Sentinel<'a, E> {
+    data_set: &'a mut [E],
+    last: Option<E>,
+}
+
+impl<'a, E> Drop for Sentinel<'a, E> {
+    fn drop(&mut self) {
+        if let (Some(last), Some(slot)) = (self.last.take(), self.data_set.last_mut()) {
+            *slot = last;
         }
     }
-    None
-}
\ No newline at end of file
+}
@@ -2,10 +2,14 @@
 // Create by VenmoSnake 2020/7/13 11:41
 //
 
-use crate::utils::time_it;
-
-mod liner;
-mod binary;
+pub mod liner;
+pub mod binary;
+pub mod exponential;
+pub mod interpolation;
+pub mod jump;
+pub mod fibonacci;
+pub mod monotone;
+pub mod stree;
 
 
 #[test]
@@ -27,13 +31,279 @@ fn test_liner_search() {
     assert_eq!(liner::search(&data_set, &b'h'), None);
 }
 
+#[test]
+fn test_liner_search_variants() {
+    use std::panic;
+
+    let data_set = vec![21, 22, 31, 4, 55, 4, 67];
+    assert_eq!(liner::find_by(&data_set, |x| *x < 10), Some(3));
+    assert_eq!(liner::find_last(&data_set, &4), Some(5));
+    assert_eq!(liner::find_last_by(&data_set, |x| *x > 30), Some(6));
+    assert_eq!(liner::find_last(&data_set, &666), None);
+    assert_eq!(liner::find_all(&data_set, &4).collect::<Vec<_>>(), vec![3, 5]);
+    assert_eq!(liner::search_many(&data_set, &[4, 67, 666, 21]), vec![Some(3), Some(6), None, Some(0)]);
+    assert_eq!(liner::search_many(&data_set, &[] as &[i32]), vec![]);
+
+    let mut data_set = data_set;
+    for ele in data_set.clone().iter().chain([0, 666].iter()) {
+        assert_eq!(liner::search_sentinel(&mut data_set, ele), liner::search(&data_set, ele));
+    }
+    assert_eq!(liner::search_sentinel(&mut [], &4), None);
+    assert_eq!(liner::search_sentinel(&mut [4], &4), Some(0));
+
+    // the last element comes back even if comparison panics
+    #[derive(Clone, Debug)]
+    struct Fragile(i32);
+    impl PartialEq for Fragile {
+        fn eq(&self, other: &Self) -> bool {
+            assert_ne!(self.0, 13);
+            self.0 == other.0
+        }
+    }
+    impl Eq for Fragile {}
+    // `eq` is reflexive whenever it returns
+    unsafe impl liner::SentinelKey for Fragile {}
+
+    let mut data_set = vec![Fragile(1), Fragile(13), Fragile(2)];
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        liner::search_sentinel(&mut data_set, &Fragile(5))
+    }));
+    assert!(result.is_err());
+    assert_eq!(data_set[2].0, 2);
+}
+
+#[test]
+fn test_binary_search() {
+    let data_set = vec![4, 21, 22, 22, 22, 31, 55, 67];
+    assert_eq!(binary::search(&data_set, &31), Some(5));
+    assert_eq!(binary::search(&data_set, &666), None);
+    assert_eq!(binary::lower_bound(&data_set, &22), 2);
+    assert_eq!(binary::upper_bound(&data_set, &22), 5);
+    assert_eq!(binary::equal_range(&data_set, &22), 2..5);
+    assert_eq!(binary::equal_range(&data_set, &30), 5..5);
+    assert_eq!(binary::equal_range(&data_set, &0), 0..0);
+    assert_eq!(binary::equal_range(&data_set, &100), 8..8);
+    assert_eq!(binary::partition_point(&data_set, |x| *x < 50), 6);
+    assert_eq!(binary::binary_search_by(&data_set, |x| x.cmp(&54)), Err(6));
+
+    let data_set: [i32; 0] = [];
+    assert_eq!(binary::search(&data_set, &4), None);
+    assert_eq!(binary::equal_range(&data_set, &4), 0..0);
+
+    let data_set = "abcdef".to_string();
+    assert_eq!(binary::search(&data_set, &b'c'), Some(2));
+    assert_eq!(binary::lower_bound(&data_set, &b'h'), 6);
+
+    // every bound agrees with a linear scan
+    let mut data_set: Vec<u8> = (0..200u32).map(|x| (x * x % 97 / 10) as u8).collect();
+    data_set.sort();
+    for ele in 0..12 {
+        let lower = data_set.iter().filter(|x| **x < ele).count();
+        let upper = data_set.iter().filter(|x| **x <= ele).count();
+        assert_eq!(binary::equal_range(&data_set, &ele), lower..upper);
+    }
+}
+
+/// check a search on sorted data against linear search, including absent elements
+#[cfg(test)]
+fn check_sorted_search<F>(search: F)
+    where F: for<'a> Fn(&'a Vec<i64>, &'a i64) -> Option<usize>
+{
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    for len in [0usize, 1, 2, 3, 5, 8, 13, 100, 1000].iter() {
+        let mut data_set: Vec<i64> = gen.uniform::<i64>(*len).into_iter().map(|x| x % 1000).collect();
+        data_set.sort();
+        data_set.dedup();
+        for ele in -1001..1001 {
+            assert_eq!(search(&data_set, &ele), liner::search(&data_set, &ele), "{} in {:?}", ele, data_set);
+        }
+    }
+}
+
+#[test]
+fn test_exponential_search() {
+    check_sorted_search(exponential::search);
+
+    let data_set = "abcdef";
+    assert_eq!(exponential::search(&data_set, &b'c'), Some(2));
+    assert_eq!(exponential::search(&data_set, &b'h'), None);
+
+    assert_eq!(exponential::search_unbounded(|i| Some(i * 3), &300), Some(100));
+    assert_eq!(exponential::search_unbounded(|i| Some(i * 3), &301), None);
+    // the probes saturate at usize::MAX and the last range is searched
+    assert_eq!(exponential::search_unbounded(Some, &usize::MAX), Some(usize::MAX));
+    assert_eq!(exponential::search_unbounded(Some, &(usize::MAX - 5)), Some(usize::MAX - 5));
+    assert_eq!(exponential::search_unbounded(|_| Some(0), &1), None);
+    assert_eq!(exponential::search_unbounded(|_| None, &0), None);
+}
+
+#[test]
+fn test_interpolation_search() {
+    check_sorted_search(interpolation::search);
+
+    // skewed keys make the estimate poor but must still be found
+    let data_set: Vec<u64> = (0..64).map(|k| 1 << k).collect();
+    for (index, ele) in data_set.iter().enumerate() {
+        assert_eq!(interpolation::search(&data_set, ele), Some(index));
+    }
+    assert_eq!(interpolation::search(&data_set, &3), None);
+    assert_eq!(interpolation::search(&[u64::MAX - 1, u64::MAX], &u64::MAX), Some(1));
+
+    let data_set = [0.5, 1.5, 2.5, 3.5];
+    assert_eq!(interpolation::search(&data_set, &3.5), Some(3));
+    assert_eq!(interpolation::search(&data_set, &f64::NAN), None);
+}
+
+#[test]
+fn test_jump_search() {
+    check_sorted_search(jump::search);
+
+    let data_set = "abcdef";
+    assert_eq!(jump::search(&data_set, &b'f'), Some(5));
+    assert_eq!(jump::search(&data_set, &b'h'), None);
+}
+
+#[test]
+fn test_fibonacci_search() {
+    check_sorted_search(fibonacci::search);
+
+    let data_set = "abcdef";
+    assert_eq!(fibonacci::search(&data_set, &b'a'), Some(0));
+    assert_eq!(fibonacci::search(&data_set, &b'h'), None);
+}
+
+#[test]
+fn test_monotone_search() {
+    use monotone::*;
+
+    // agrees with a scan of every value of small types
+    for threshold in -128..=128i32 {
+        let expected = (-128..=127).find(|x| *x >= threshold).map(|x| x as i8);
+        assert_eq!(search_monotone(i8::MIN, i8::MAX, |x| x as i32 >= threshold), expected);
+    }
+    assert_eq!(search_monotone(u64::MAX - 3, u64::MAX, |x| x == u64::MAX), Some(u64::MAX));
+    assert_eq!(search_monotone(0usize, 0, |_| true), Some(0));
+    assert_eq!(search_monotone(5usize, 4, |_| true), None);
+
+    let mut calls = 0;
+    search_monotone(0u64, 1 << 40, |x| {
+        calls += 1;
+        x >= 12345
+    });
+    assert!(calls <= 42, "{} calls", calls);
+
+    let root = bisect(0.0, 10.0, 1e-12, 200, |x| x * x * x >= 10.0);
+    assert!(root * root * root >= 10.0);
+    assert!((root - 10f64.cbrt()).abs() < 1e-9);
+    // iteration limit stops early
+    let rough = bisect(0.0, 1024.0, 0.0, 3, |x| x >= 1.0);
+    assert_eq!(rough, 128.0);
+
+    let f = |x: f64| (x - 1.5).abs() + 2.0;
+    assert!((ternary_search(-100.0, 100.0, 1e-9, 500, f) - 1.5).abs() < 1e-6);
+    assert!((golden_section_search(-100.0, 100.0, 1e-9, 500, f) - 1.5).abs() < 1e-6);
+    // negated to find maximum
+    let g = |x: f64| -(x * (4.0 - x));
+    assert!((ternary_search(0.0, 4.0, 1e-9, 500, g) - 2.0).abs() < 1e-6);
+    assert!((golden_section_search(0.0, 4.0, 1e-9, 500, g) - 2.0).abs() < 1e-6);
+    // eps of zero ends by the iteration limit
+    assert!((golden_section_search(0.0, 4.0, 0.0, 200, g) - 2.0).abs() < 1e-6);
+
+    let mut calls = 0;
+    golden_section_search(0.0, 4.0, 0.0, 50, |x| {
+        calls += 1;
+        g(x)
+    });
+    assert_eq!(calls, 52);
+}
+
+/// binary searches never make more than ⌈log2 n⌉ + 1 comparisons
+#[test]
+fn test_binary_search_comparisons() {
+    use crate::utils::counted::{measure, Counted};
+
+    for len in [1usize, 2, 3, 7, 8, 9, 100, 1000, 1024, 1025].iter() {
+        let data_set: Vec<Counted<usize>> = (0..*len).map(|x| Counted(x * 2)).collect();
+        let bound = (usize::BITS - (len - 1).leading_zeros()) as u64 + 1;
+        for ele in 0..len * 2 + 1 {
+            let ele = Counted(ele);
+            let (_, report) = measure(|| binary::search(&data_set, &ele));
+            assert!(report.comparisons <= bound, "search {} in {}: {}", ele, len, report);
+            let (_, report) = measure(|| binary::lower_bound(&data_set, &ele));
+            assert!(report.comparisons <= bound, "lower bound {} in {}: {}", ele, len, report);
+            let (_, report) = measure(|| binary::branchless_lower_bound(&data_set, &ele));
+            assert!(report.comparisons <= bound, "branchless {} in {}: {}", ele, len, report);
+            assert_eq!(report.clones, 0);
+        }
+    }
+}
+
+#[test]
+fn test_sorted_layouts() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    for len in [0usize, 1, 2, 15, 16, 17, 100, 272, 273, 1000].iter() {
+        let mut data_set: Vec<u32> = gen.uniform::<u32>(*len).into_iter().map(|x| x % 5000).collect();
+        data_set.sort();
+        let layout = binary::Eytzinger::new(&data_set);
+        let tree = stree::STree::new(&data_set);
+        assert_eq!(layout.len(), *len);
+        assert_eq!(tree.len(), *len);
+        for ele in 0..5001 {
+            let lower = binary::lower_bound(&data_set, &ele);
+            assert_eq!(binary::branchless_lower_bound(&data_set, &ele), lower);
+            assert_eq!(binary::branchless_search(&data_set, &ele).is_some(), data_set.get(lower) == Some(&ele));
+            assert_eq!(layout.lower_bound(&ele).map(|index| layout[index]), data_set.get(lower).copied());
+            assert_eq!(layout.search(&ele).is_some(), data_set.get(lower) == Some(&ele));
+            assert_eq!(tree.lower_bound(ele), data_set.get(lower).copied());
+        }
+    }
+
+    // the max key must be told from padding
+    let tree = stree::STree::new(&[1u64, 5, u64::MAX]);
+    assert_eq!(tree.lower_bound(6), Some(u64::MAX));
+    assert!(tree.contains(u64::MAX));
+    let tree = stree::STree::new(&[1u64, 5]);
+    assert_eq!(tree.lower_bound(6), None);
+    assert!(!tree.contains(u64::MAX));
+}
+
+#[test]
+fn bench_sorted_layouts() {
+    use crate::utils::bench::Bench;
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let mut data_set: Vec<u32> = gen.uniform(1 << 20);
+    data_set.sort();
+    let queries: Vec<u32> = gen.uniform(1000);
+    let layout = binary::Eytzinger::new(&data_set);
+    let tree = stree::STree::new(&data_set);
+    let bench = Bench { iterations: 100, ..Bench::new() };
+    println!("binary search: {}", bench.run(|| {
+        queries.iter().map(|ele| binary::lower_bound(&data_set, ele)).sum::<usize>()
+    }));
+    println!("branchless binary search: {}", bench.run(|| {
+        queries.iter().map(|ele| binary::branchless_lower_bound(&data_set, ele)).sum::<usize>()
+    }));
+    println!("eytzinger: {}", bench.run(|| {
+        queries.iter().filter_map(|ele| layout.lower_bound(ele)).sum::<usize>()
+    }));
+    println!("s-tree: {}", bench.run(|| {
+        queries.iter().filter_map(|ele| tree.lower_bound(*ele)).map(|x| x as u64).sum::<u64>()
+    }));
+}
+
 #[test]
 fn bench_liner_search() {
-    use random::Source;
+    use crate::utils::bench::Bench;
+    use crate::utils::gen::Gen;
 
-    let mut gen = random::default().seed([0, 100]);
-    let data_set: Vec<i32> = gen.iter().take(30).collect();
-    time_it(|| {
-        liner::search(&data_set, &4);
-    }, 1000);
+    let mut gen = Gen::new(100);
+    let data_set: Vec<i32> = gen.uniform(30);
+    let bench = Bench { iterations: 1000, ..Bench::new() };
+    println!("{}", bench.run(|| liner::search(&data_set, &4)));
 }
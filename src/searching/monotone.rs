@@ -0,0 +1,153 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/17 15:20
+//
+
+/// integer domain of `search_monotone`, implemented for all primitive integers
+pub trait Integer: Copy + Ord {
+    /// floor of the average of `lo` and `hi` without overflow
+    fn midpoint(lo: Self, hi: Self) -> Self;
+    /// the next integer, never called on the max value
+    fn successor(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {
+        $(impl Integer for $t {
+            fn midpoint(lo: Self, hi: Self) -> Self {
+                // arithmetic shift keeps it floor for negative numbers
+                (lo & hi) + ((lo ^ hi) >> 1)
+            }
+
+            fn successor(self) -> Self {
+                self + 1
+            }
+        })*
+    };
+}
+
+impl_integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+/// binary search on the answer, return the smallest `x` in `[lo, hi]` making `pred` true,
+/// `pred` must be monotone: once true it stays true for greater `x`,
+/// return `None` if it's false in the whole range
+/// 时间复杂度: O(log(hi - lo)) 次pred调用
+///```
+/// use algorithms_rs::searching::monotone::search_monotone;
+///
+/// // the smallest x with x * x >= 2020
+/// assert_eq!(search_monotone(0u64, 1 << 31, |x| x * x >= 2020), Some(45));
+/// assert_eq!(search_monotone(i32::MIN, i32::MAX, |x| x >= -7), Some(-7));
+/// assert_eq!(search_monotone(0, 100, |x| x > 100), None);
+/// ```
+pub fn search_monotone<I, P>(lo: I, hi: I, mut pred: P) -> Option<I>
+    where I: Integer,
+          P: FnMut(I) -> bool
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        let mid = I::midpoint(lo, hi);
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid.successor();
+        }
+    }
+    Some(lo)
+}
+
+/// bisection over floats, return `x` close to the boundary in `[lo, hi]` where `pred` turns true,
+/// `pred` must be monotone and true at `hi`, the returned `x` always makes it true
+/// stop when the range is not wider than `eps` or after `max_iter` halvings
+/// 时间复杂度: O(min(log((hi - lo) / eps), max_iter)) 次pred调用
+///```
+/// use algorithms_rs::searching::monotone::bisect;
+///
+/// let sqrt2 = bisect(0.0, 2.0, 1e-12, 100, |x| x * x >= 2.0);
+/// assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-11);
+/// ```
+pub fn bisect<P>(mut lo: f64, mut hi: f64, eps: f64, max_iter: usize, mut pred: P) -> f64
+    where P: FnMut(f64) -> bool
+{
+    for _ in 0..max_iter {
+        if hi - lo <= eps {
+            break;
+        }
+        let mid = lo + (hi - lo) / 2.0;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+/// ternary search, return `x` in `[lo, hi]` minimizing unimodal `f`,
+/// which decreases then increases, negate `f` to find a maximum
+/// every round drops a third of the range by comparing `f` at two inner points
+/// 时间复杂度: O(min(log((hi - lo) / eps), max_iter)) 次f调用
+///```
+/// use algorithms_rs::searching::monotone::ternary_search;
+///
+/// let x = ternary_search(-10.0, 10.0, 1e-9, 200, |x| (x - 3.0) * (x - 3.0));
+/// assert!((x - 3.0).abs() < 1e-6);
+/// ```
+pub fn ternary_search<F>(mut lo: f64, mut hi: f64, eps: f64, max_iter: usize, mut f: F) -> f64
+    where F: FnMut(f64) -> f64
+{
+    for _ in 0..max_iter {
+        if hi - lo <= eps {
+            break;
+        }
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if f(m1) < f(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+/// golden-section search, return `x` in `[lo, hi]` minimizing unimodal `f`, see `ternary_search`
+/// the inner points split the range by golden ratio, so one of them is reused by next round
+/// and only one call of `f` is needed per round
+/// 时间复杂度: O(min(log((hi - lo) / eps), max_iter)) 次f调用
+///```
+/// use algorithms_rs::searching::monotone::golden_section_search;
+///
+/// let x = golden_section_search(3.0, 6.0, 1e-9, 200, |x| x.sin());
+/// assert!((x - 3.0 * std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+/// ```
+pub fn golden_section_search<F>(mut lo: f64, mut hi: f64, eps: f64, max_iter: usize, mut f: F) -> f64
+    where F: FnMut(f64) -> f64
+{
+    // 1 / golden ratio
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut m1 = hi - ratio * (hi - lo);
+    let mut m2 = lo + ratio * (hi - lo);
+    let (mut f1, mut f2) = (f(m1), f(m2));
+    for _ in 0..max_iter {
+        if hi - lo <= eps {
+            break;
+        }
+        if f1 < f2 {
+            hi = m2;
+            m2 = m1;
+            f2 = f1;
+            m1 = hi - ratio * (hi - lo);
+            f1 = f(m1);
+        } else {
+            lo = m1;
+            m1 = m2;
+            f1 = f2;
+            m2 = lo + ratio * (hi - lo);
+            f2 = f(m2);
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
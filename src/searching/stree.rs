@@ -0,0 +1,117 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/18 14:02
+//
+
+/// keys per node, a node of `u32` fills one cache line
+const B: usize = 16;
+
+/// key of `STree`, implemented for `u32` and `u64`
+pub trait STreeKey: Copy + Ord {
+    /// padding of the last node, greater than or equal to every key
+    const MAX: Self;
+}
+
+impl STreeKey for u32 {
+    const MAX: Self = u32::MAX;
+}
+
+impl STreeKey for u64 {
+    const MAX: Self = u64::MAX;
+}
+
+#[derive(Clone, Copy)]
+#[repr(align(64))]
+struct Node<K>([K; B]);
+
+/// static B-tree ("S-tree") over sorted keys, nodes of `B` keys are laid out like Eytzinger layout
+/// with `B + 1` children: the children of node `k` are `k * (B + 1) + i + 1` for `i` in `0..=B`
+/// every lookup touches one cache line per level and there are only log_17(n) levels,
+/// the rank inside a node is counted over all keys without branch so it's vectorized
+/// 时间复杂度: O(logn)
+/// 空间复杂度: O(n)
+///```
+/// use algorithms_rs::searching::stree::STree;
+///
+/// let keys: Vec<u32> = (0..1000).map(|x| x * 3).collect();
+/// let tree = STree::new(&keys);
+/// assert_eq!(tree.lower_bound(301), Some(303));
+/// assert_eq!(tree.lower_bound(3000), None);
+/// assert!(tree.contains(999));
+/// assert!(!tree.contains(1000));
+/// ```
+pub struct STree<K> {
+    nodes: Vec<Node<K>>,
+    len: usize,
+    // `MAX` as real key, to tell it from padding
+    has_max: bool,
+}
+
+impl<K: STreeKey> STree<K> {
+    /// build tree from sorted keys
+    /// 时间复杂度: O(n)
+    pub fn new(sorted: &[K]) -> Self {
+        let mut nodes = vec![Node([K::MAX; B]); sorted.len().div_ceil(B)];
+        let mut keys = sorted.iter().copied();
+        Self::fill(&mut nodes, 0, &mut keys);
+        Self {
+            nodes,
+            len: sorted.len(),
+            has_max: sorted.last() == Some(&K::MAX),
+        }
+    }
+
+    /// fill the subtree rooted at `k` by in-order traversal, slots left at the end keep padding
+    fn fill<I>(nodes: &mut [Node<K>], k: usize, keys: &mut I)
+        where I: Iterator<Item=K>
+    {
+        if k >= nodes.len() {
+            return;
+        }
+        for i in 0..B {
+            Self::fill(nodes, Self::child(k, i), keys);
+            if let Some(key) = keys.next() {
+                nodes[k].0[i] = key;
+            }
+        }
+        Self::fill(nodes, Self::child(k, B), keys);
+    }
+
+    fn child(k: usize, i: usize) -> usize {
+        k * (B + 1) + i + 1
+    }
+
+    /// return the least key not less than `key`, `None` if all are less
+    pub fn lower_bound(&self, key: K) -> Option<K> {
+        let mut result = K::MAX;
+        let mut k = 0;
+        while k < self.nodes.len() {
+            let node = &self.nodes[k].0;
+            let rank: usize = node.iter().map(|x| (*x < key) as usize).sum();
+            if rank < B {
+                result = node[rank];
+            }
+            k = Self::child(k, rank);
+        }
+        // padding is `MAX`, so `MAX` is only the answer if it's a real key
+        if result == K::MAX && !self.has_max {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// return true if `key` is in the tree
+    pub fn contains(&self, key: K) -> bool {
+        self.lower_bound(key) == Some(key)
+    }
+
+    /// return the number of keys
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// return true if there is no key
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -1,3 +1,36 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::Sorter;
+
+/// bubble sort, swap adjacent elements until nothing is swapped
+/// 时间复杂度: O(n^2), 已排序的输入为O(n)
+/// 空间复杂度: O(1)
+pub struct BubbleSort;
+
+impl Sorter for BubbleSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = true;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        let mut n = arr.len();
+        while n > 1 {
+            // everything after the last swap is already in place
+            let mut last = 0;
+            for i in 1..n {
+                if compare(&arr[i - 1], &arr[i]) == Ordering::Greater {
+                    counted::swap(arr, i - 1, i);
+                    last = i;
+                }
+            }
+            n = last;
+        }
+    }
+}
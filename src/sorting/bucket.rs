@@ -1,3 +1,488 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::{insertion, merge, Sorter};
+
+/// bucket size that is finished by insertion sort
+const SMALL_BUCKET: usize = 16;
+
+/// count table larger than this is refused by `counting_sort`
+const MAX_COUNTING_RANGE: u128 = 1 << 24;
+
+/// bucket sort over any comparable elements
+/// about sqrt(n) splitters are sampled from the input, every element is distributed into the bucket
+/// between two splitters, then every bucket is sorted on its own
+/// 时间复杂度: 平均O(nlogn)
+/// 空间复杂度: O(n)
+pub struct BucketSort;
+
+impl Sorter for BucketSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = false;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        bucket_sort(arr, &mut compare)
+    }
+}
+
+fn bucket_sort<T, F>(arr: &mut [T], compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len <= SMALL_BUCKET {
+        insertion::sort_by(arr, compare);
+        return;
+    }
+    let buckets = (len as f64).sqrt() as usize;
+    let mut splitters: Vec<usize> = (1..buckets).map(|i| i * len / buckets).collect();
+    insertion::sort_by(&mut splitters, &mut |a: &usize, b: &usize| compare(&arr[*a], &arr[*b]));
+    splitters.dedup_by(|a, b| compare(&arr[*a], &arr[*b]) == Ordering::Equal);
+
+    // bucket of element is the number of splitters not greater than it
+    let ids: Vec<usize> = arr.iter()
+        .map(|ele| splitters.partition_point(|s| compare(&arr[*s], ele) != Ordering::Greater))
+        .collect();
+    let mut offsets = vec![0; splitters.len() + 3];
+    for id in ids.iter() {
+        offsets[id + 2] += 1;
+    }
+    for i in 2..offsets.len() {
+        offsets[i] += offsets[i - 1];
+    }
+    // destination of every element, elements in the same bucket keep their order
+    let dest: Vec<usize> = ids.iter().map(|id| {
+        offsets[id + 1] += 1;
+        offsets[id + 1] - 1
+    }).collect();
+    move_to(arr, dest);
+
+    // `offsets[i]..offsets[i + 1]` is bucket `i` now
+    for i in 0..offsets.len() - 2 {
+        let bucket = &mut arr[offsets[i]..offsets[i + 1]];
+        if bucket.len() == len {
+            // every element falls into one bucket, splitting again won't make progress
+            merge::sort_by(bucket, compare);
+        } else {
+            bucket_sort(bucket, compare);
+        }
+    }
+}
+
+/// move `arr[i]` to `arr[dest[i]]` for every `i`, `dest` must be a permutation
+fn move_to<T>(arr: &mut [T], mut dest: Vec<usize>) {
+    for i in 0..arr.len() {
+        while dest[i] != i {
+            let d = dest[i];
+            counted::swap(arr, i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+/// bucket sort over floats uniformly distributed in [0, 1),
+/// `n` buckets cover the range evenly and every bucket is sorted by insertion sort
+/// 时间复杂度: 平均O(n)
+/// 空间复杂度: O(n)
+/// # Panics
+/// panics if any element is not in [0, 1)
+/// ```
+/// use algorithms_rs::sorting::bucket::unit_bucket_sort;
+///
+/// let mut arr = vec![0.78, 0.17, 0.39, 0.26, 0.72, 0.94, 0.21, 0.12, 0.23, 0.68];
+/// unit_bucket_sort(&mut arr);
+/// assert_eq!(arr, vec![0.12, 0.17, 0.21, 0.23, 0.26, 0.39, 0.68, 0.72, 0.78, 0.94]);
+/// ```
+pub fn unit_bucket_sort<F>(arr: &mut [F])
+    where F: Copy + PartialOrd + Into<f64>
+{
+    let len = arr.len();
+    let mut buckets: Vec<Vec<F>> = vec![Vec::new(); len];
+    for ele in arr.iter() {
+        let value: f64 = (*ele).into();
+        assert!((0.0..1.0).contains(&value), "element {} is not in [0, 1)", value);
+        buckets[((value * len as f64) as usize).min(len - 1)].push(*ele);
+    }
+    let mut i = 0;
+    for bucket in buckets.iter_mut() {
+        insertion::sort_by(bucket, &mut |a: &F, b: &F| a.partial_cmp(b).unwrap());
+        arr[i..i + bucket.len()].copy_from_slice(bucket);
+        i += bucket.len();
+    }
+}
+
+/// key of radix sort, seen as `LEVELS` bytes compared from the most significant one
+/// implement it for own types to radix sort them by a derived key
+/// ```
+/// use algorithms_rs::sorting::bucket::{lsd_radix_sort, RadixKey};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Order {
+///     id: u32,
+///     price: i16,
+/// }
+///
+/// // sort by price then id
+/// impl RadixKey for Order {
+///     const LEVELS: usize = 6;
+///
+///     fn key_byte(&self, level: usize) -> u8 {
+///         if level < 4 {
+///             self.id.key_byte(level)
+///         } else {
+///             self.price.key_byte(level - 4)
+///         }
+///     }
+/// }
+///
+/// let mut orders = vec![Order { id: 3, price: 10 }, Order { id: 1, price: -5 }, Order { id: 2, price: 10 }];
+/// lsd_radix_sort(&mut orders);
+/// assert_eq!(orders.iter().map(|o| o.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+/// ```
+pub trait RadixKey {
+    /// number of bytes of the key
+    const LEVELS: usize;
+
+    /// byte of the key at `level`, level 0 is the least significant byte
+    fn key_byte(&self, level: usize) -> u8;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(impl RadixKey for $t {
+            const LEVELS: usize = std::mem::size_of::<$t>();
+
+            #[inline]
+            fn key_byte(&self, level: usize) -> u8 {
+                (*self >> (level * 8)) as u8
+            }
+        })*
+    }
+}
+
+macro_rules! impl_signed_radix_key {
+    ($($t:ty => $u:ty),*) => {
+        $(impl RadixKey for $t {
+            const LEVELS: usize = std::mem::size_of::<$t>();
+
+            /// the sign bit is flipped so negative numbers are ordered before positive ones
+            #[inline]
+            fn key_byte(&self, level: usize) -> u8 {
+                ((*self as $u ^ (1 << (<$u>::BITS - 1))) >> (level * 8)) as u8
+            }
+        })*
+    }
+}
+
+impl_radix_key!(u8, u16, u32, u64, u128, usize);
+impl_signed_radix_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// least significant digit radix sort, stable counting sort on every byte from the lowest one,
+/// bytes that are equal for every element are skipped
+/// 时间复杂度: O(n * LEVELS)
+/// 空间复杂度: O(n)
+/// ```
+/// use algorithms_rs::sorting::bucket::lsd_radix_sort;
+///
+/// let mut arr = vec![170i32, -45, 75, -90, 802, 24, 2, 66];
+/// lsd_radix_sort(&mut arr);
+/// assert_eq!(arr, vec![-90, -45, 2, 24, 66, 75, 170, 802]);
+/// ```
+pub fn lsd_radix_sort<T>(arr: &mut [T])
+    where T: RadixKey + Copy
+{
+    let len = arr.len();
+    if len < 2 {
+        return;
+    }
+    let mut buf = arr.to_vec();
+    // the sorted elements are in `arr` when true, otherwise in `buf`
+    let mut in_arr = true;
+    for level in 0..T::LEVELS {
+        let (src, dest) = if in_arr { (&*arr, &mut buf[..]) } else { (&buf[..], &mut *arr) };
+        let mut offsets = match byte_offsets(src.iter().map(|ele| ele.key_byte(level))) {
+            Some(offsets) => offsets,
+            None => continue,
+        };
+        for ele in src.iter() {
+            let byte = ele.key_byte(level) as usize;
+            dest[offsets[byte]] = *ele;
+            offsets[byte] += 1;
+        }
+        in_arr = !in_arr;
+    }
+    if !in_arr {
+        arr.copy_from_slice(&buf);
+    }
+}
+
+/// least significant digit radix sort by a derived key, the key is extracted once per element,
+/// the sorted order is found on indices and applied to `arr` at the end
+/// ```
+/// use algorithms_rs::sorting::bucket::lsd_radix_sort_by_key;
+///
+/// let mut arr = vec!["ccc", "a", "bb", "dd"];
+/// lsd_radix_sort_by_key(&mut arr, |s| s.len());
+/// assert_eq!(arr, vec!["a", "bb", "dd", "ccc"]);
+/// ```
+pub fn lsd_radix_sort_by_key<T, K, F>(arr: &mut [T], key: F)
+    where K: RadixKey,
+          F: FnMut(&T) -> K
+{
+    let len = arr.len();
+    if len < 2 {
+        return;
+    }
+    let keys: Vec<K> = arr.iter().map(key).collect();
+    let mut order: Vec<usize> = (0..len).collect();
+    let mut buf = vec![0; len];
+    for level in 0..K::LEVELS {
+        let mut offsets = match byte_offsets(order.iter().map(|i| keys[*i].key_byte(level))) {
+            Some(offsets) => offsets,
+            None => continue,
+        };
+        for i in order.iter() {
+            let byte = keys[*i].key_byte(level) as usize;
+            buf[offsets[byte]] = *i;
+            offsets[byte] += 1;
+        }
+        std::mem::swap(&mut order, &mut buf);
+    }
+    // `order[pos]` is the index of element placed at `pos`
+    let mut dest = buf;
+    for (pos, i) in order.iter().enumerate() {
+        dest[*i] = pos;
+    }
+    move_to(arr, dest);
+}
+
+/// return the start offset of every byte value, `None` if all bytes are the same
+fn byte_offsets<I>(bytes: I) -> Option<[usize; 256]>
+    where I: Iterator<Item=u8>
+{
+    let mut counts = [0usize; 256];
+    let mut len = 0;
+    for byte in bytes {
+        counts[byte as usize] += 1;
+        len += 1;
+    }
+    if counts.contains(&len) {
+        return None;
+    }
+    let mut sum = 0;
+    for count in counts.iter_mut() {
+        let start = sum;
+        sum += *count;
+        *count = start;
+    }
+    Some(counts)
+}
+
+/// most significant digit radix sort, in place american flag sort on the highest byte,
+/// then every bucket is sorted on the next byte recursively, small buckets use insertion sort
+/// 时间复杂度: O(n * LEVELS)
+/// 空间复杂度: O(LEVELS)
+/// ```
+/// use algorithms_rs::sorting::bucket::msd_radix_sort;
+///
+/// let mut arr = vec![170u64, 45, 75, 90, 802, 24, 2, 66];
+/// msd_radix_sort(&mut arr);
+/// assert_eq!(arr, vec![2, 24, 45, 66, 75, 90, 170, 802]);
+/// ```
+pub fn msd_radix_sort<T: RadixKey>(arr: &mut [T]) {
+    if T::LEVELS > 0 {
+        american_flag(arr, T::LEVELS - 1, &mut |ele: &T, level| ele.key_byte(level));
+    }
+}
+
+/// most significant digit radix sort by a derived key, the key is extracted on every visit
+pub fn msd_radix_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+    where K: RadixKey,
+          F: FnMut(&T) -> K
+{
+    if K::LEVELS > 0 {
+        american_flag(arr, K::LEVELS - 1, &mut |ele: &T, level| key(ele).key_byte(level));
+    }
+}
+
+fn american_flag<T, B>(arr: &mut [T], level: usize, byte: &mut B)
+    where B: FnMut(&T, usize) -> u8
+{
+    if arr.len() <= SMALL_BUCKET {
+        insertion::sort_by(arr, &mut |a: &T, b: &T| {
+            for level in (0..=level).rev() {
+                match byte(a, level).cmp(&byte(b, level)) {
+                    Ordering::Equal => continue,
+                    order => return order,
+                }
+            }
+            Ordering::Equal
+        });
+        return;
+    }
+    let mut starts = match byte_offsets(arr.iter().map(|ele| byte(ele, level))) {
+        Some(offsets) => offsets,
+        None if level > 0 => return american_flag(arr, level - 1, byte),
+        None => return,
+    };
+    let mut ends = [arr.len(); 256];
+    ends[..255].copy_from_slice(&starts[1..]);
+    // swap every element into its bucket, `starts[b]` is the first unplaced slot of bucket `b`
+    for b in 0..256 {
+        while starts[b] < ends[b] {
+            let target = byte(&arr[starts[b]], level) as usize;
+            if target == b {
+                starts[b] += 1;
+            } else {
+                counted::swap(arr, starts[b], starts[target]);
+                starts[target] += 1;
+            }
+        }
+    }
+    if level == 0 {
+        return;
+    }
+    let mut start = 0;
+    for end in ends.iter() {
+        if end - start > 1 {
+            american_flag(&mut arr[start..*end], level - 1, byte);
+        }
+        start = *end;
+    }
+}
+
+/// counting sort for integers in a small range, elements are counted by `value - min`
+/// the sort is stable, so elements equal by key keep their order
+/// 时间复杂度: O(n + k) k为最大值与最小值之差
+/// 空间复杂度: O(n + k)
+/// # Panics
+/// panics if the range of elements is larger than 2^24 or the key is wider than 128 bits
+/// ```
+/// use algorithms_rs::sorting::bucket::counting_sort;
+///
+/// let mut arr = vec![3i8, -1, 2, 3, -1, 0];
+/// counting_sort(&mut arr);
+/// assert_eq!(arr, vec![-1, -1, 0, 2, 3, 3]);
+/// ```
+pub fn counting_sort<T: RadixKey>(arr: &mut [T]) {
+    assert!(T::LEVELS <= 16, "key is wider than 128 bits");
+    let keys: Vec<u128> = arr.iter()
+        .map(|ele| (0..T::LEVELS).fold(0, |key, level| key | (ele.key_byte(level) as u128) << (level * 8)))
+        .collect();
+    let min = match keys.iter().min() {
+        Some(min) => *min,
+        None => return,
+    };
+    let range = keys.iter().max().unwrap() - min + 1;
+    assert!(range <= MAX_COUNTING_RANGE, "range {} is too large for counting sort", range);
+    let mut keys = keys.into_iter();
+    counting_sort_by_key(arr, range as usize, |_| (keys.next().unwrap() - min) as usize)
+}
+
+/// stable counting sort by a key in `0..range`
+/// # Panics
+/// panics if any key is not less than `range`
+/// ```
+/// use algorithms_rs::sorting::bucket::counting_sort_by_key;
+///
+/// let mut arr = vec![("b", 1), ("a", 0), ("c", 1), ("d", 0)];
+/// counting_sort_by_key(&mut arr, 2, |x| x.1);
+/// assert_eq!(arr, vec![("a", 0), ("d", 0), ("b", 1), ("c", 1)]);
+/// ```
+pub fn counting_sort_by_key<T, F>(arr: &mut [T], range: usize, key: F)
+    where F: FnMut(&T) -> usize
+{
+    let keys: Vec<usize> = arr.iter().map(key).collect();
+    let mut offsets = vec![0; range + 1];
+    for key in keys.iter() {
+        assert!(*key < range, "key {} is out of range {}", key, range);
+        offsets[key + 1] += 1;
+    }
+    for i in 1..offsets.len() {
+        offsets[i] += offsets[i - 1];
+    }
+    let dest: Vec<usize> = keys.iter().map(|key| {
+        offsets[*key] += 1;
+        offsets[*key] - 1
+    }).collect();
+    move_to(arr, dest);
+}
+
+#[test]
+fn test_radix_sort() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    macro_rules! check {
+        ($($t:ty),*) => {
+            $(for len in [0usize, 1, 15, 17, 300, 2000].iter() {
+                let data_set: Vec<$t> = gen.uniform(*len);
+                let mut expected = data_set.clone();
+                expected.sort();
+
+                let mut arr = data_set.clone();
+                lsd_radix_sort(&mut arr);
+                assert_eq!(arr, expected);
+                let mut arr = data_set.clone();
+                msd_radix_sort(&mut arr);
+                assert_eq!(arr, expected);
+                let mut arr = data_set.clone();
+                lsd_radix_sort_by_key(&mut arr, |x| *x);
+                assert_eq!(arr, expected);
+                let mut arr = data_set.clone();
+                msd_radix_sort_by_key(&mut arr, |x| *x);
+                assert_eq!(arr, expected);
+                // small range
+                let mut arr: Vec<$t> = data_set.iter().map(|x| *x % 100).collect();
+                let mut expected = arr.clone();
+                expected.sort();
+                counting_sort(&mut arr);
+                assert_eq!(arr, expected);
+            })*
+        }
+    }
+    check!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+    let wide: Vec<i128> = gen.uniform::<i64>(500).into_iter().map(|x| (x as i128) << 64 | x as u64 as i128).collect();
+    let mut expected = wide.clone();
+    expected.sort();
+    let mut arr = wide.clone();
+    lsd_radix_sort(&mut arr);
+    assert_eq!(arr, expected);
+    let mut arr = wide;
+    msd_radix_sort(&mut arr);
+    assert_eq!(arr, expected);
+
+    // by key sorts are stable
+    let pairs: Vec<(u16, usize)> = gen.uniform::<u16>(1000).into_iter().map(|x| x % 50).zip(0..).collect();
+    let mut expected = pairs.clone();
+    expected.sort_by_key(|x| x.0);
+    let mut arr = pairs.clone();
+    lsd_radix_sort_by_key(&mut arr, |x| x.0);
+    assert_eq!(arr, expected);
+    let mut arr = pairs.clone();
+    counting_sort_by_key(&mut arr, 50, |x| x.0 as usize);
+    assert_eq!(arr, expected);
+}
+
+#[test]
+fn test_unit_bucket_sort() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let data_set: Vec<f64> = gen.uniform::<f64>(1000).into_iter().map(|x| x.min(0.999)).collect();
+    let mut expected = data_set.clone();
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut arr = data_set.clone();
+    unit_bucket_sort(&mut arr);
+    assert_eq!(arr, expected);
+
+    let mut arr: Vec<f32> = data_set.iter().map(|x| *x as f32).collect();
+    unit_bucket_sort(&mut arr);
+    assert!(arr.windows(2).all(|w| w[0] <= w[1]));
+}
@@ -1,3 +1,264 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::Sorter;
+
+/// heap sort, build a max heap then move the top to the end one by one
+/// 时间复杂度: O(nlogn)
+/// 空间复杂度: O(1)
+pub struct HeapSort;
+
+impl Sorter for HeapSort {
+    const STABLE: bool = false;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort_by(arr, &mut compare)
+    }
+}
+
+/// heap sort used by other algorithms as O(nlogn) fallback
+pub fn sort_by<T, F>(arr: &mut [T], compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    heapify_by(arr, &mut *compare);
+    sort_heap_by(arr, compare);
+}
+
+// Heap primitives over slices like the C++ `<algorithm>` heap functions.
+// The heap is a max heap by `compare`: no element compares greater than its parent, the children
+// of `arr[i]` are `arr[2i + 1]` and `arr[2i + 2]`.
+
+/// rearrange `arr` into a max heap
+/// 时间复杂度: O(n)
+/// ```
+/// use algorithms_rs::sorting::heap::{heapify, is_heap};
+///
+/// let mut arr = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// heapify(&mut arr);
+/// assert_eq!(arr[0], 9);
+/// assert!(is_heap(&arr));
+/// ```
+pub fn heapify<T: Ord>(arr: &mut [T]) {
+    heapify_by(arr, T::cmp)
+}
+
+/// rearrange `arr` into a max heap by `compare`
+pub fn heapify_by<T, F>(arr: &mut [T], mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    for i in (0..arr.len() / 2).rev() {
+        sift_down_by(arr, i, &mut compare);
+    }
+}
+
+/// `arr[..len - 1]` is a heap, push the last element into it
+/// 时间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::heap::{is_heap, push_heap};
+///
+/// let mut arr = vec![5, 3, 4];
+/// arr.push(6);
+/// push_heap(&mut arr);
+/// assert_eq!(arr[0], 6);
+/// assert!(is_heap(&arr));
+/// ```
+pub fn push_heap<T: Ord>(arr: &mut [T]) {
+    push_heap_by(arr, T::cmp)
+}
+
+/// `arr[..len - 1]` is a heap by `compare`, push the last element into it
+pub fn push_heap_by<T, F>(arr: &mut [T], compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    if !arr.is_empty() {
+        sift_up_by(arr, arr.len() - 1, compare)
+    }
+}
+
+/// `arr` is a heap, move the top to the end and make `arr[..len - 1]` a heap again
+/// 时间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::heap::{heapify, is_heap, pop_heap};
+///
+/// let mut arr = vec![3, 1, 4, 1, 5];
+/// heapify(&mut arr);
+/// pop_heap(&mut arr);
+/// assert_eq!(arr.pop(), Some(5));
+/// assert!(is_heap(&arr));
+/// ```
+pub fn pop_heap<T: Ord>(arr: &mut [T]) {
+    pop_heap_by(arr, T::cmp)
+}
+
+/// `arr` is a heap by `compare`, move the top to the end and make `arr[..len - 1]` a heap again
+pub fn pop_heap_by<T, F>(arr: &mut [T], compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len > 1 {
+        counted::swap(arr, 0, len - 1);
+        sift_down_by(&mut arr[..len - 1], 0, compare);
+    }
+}
+
+/// `arr` is a heap, sort it ascending by popping the top repeatedly
+/// 时间复杂度: O(nlogn)
+pub fn sort_heap<T: Ord>(arr: &mut [T]) {
+    sort_heap_by(arr, T::cmp)
+}
+
+/// `arr` is a heap by `compare`, sort it ascending by popping the top repeatedly
+pub fn sort_heap_by<T, F>(arr: &mut [T], mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    for end in (2..=arr.len()).rev() {
+        pop_heap_by(&mut arr[..end], &mut compare);
+    }
+}
+
+/// move `arr[index]` up until its parent is not less than it, the rest of `arr` must be a heap
+/// 时间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::heap::{heapify, is_heap, sift_up};
+///
+/// let mut arr = vec![3, 1, 4, 1, 5];
+/// heapify(&mut arr);
+/// arr[4] = 10;
+/// sift_up(&mut arr, 4);
+/// assert_eq!(arr[0], 10);
+/// assert!(is_heap(&arr));
+/// ```
+pub fn sift_up<T: Ord>(arr: &mut [T], index: usize) {
+    sift_up_by(arr, index, T::cmp)
+}
+
+/// move `arr[index]` up by `compare` until its parent is not less than it,
+/// the rest of `arr` must be a heap by `compare`
+pub fn sift_up_by<T, F>(arr: &mut [T], mut index: usize, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    while index > 0 {
+        let parent = (index - 1) / 2;
+        if compare(&arr[parent], &arr[index]) != Ordering::Less {
+            break;
+        }
+        counted::swap(arr, parent, index);
+        index = parent;
+    }
+}
+
+/// move `arr[index]` down until both children are not greater than it,
+/// the subtrees of `arr[index]` must be heaps
+/// 时间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::heap::{heapify, is_heap, sift_down};
+///
+/// let mut arr = vec![3, 1, 4, 1, 5];
+/// heapify(&mut arr);
+/// arr[0] = 0;
+/// sift_down(&mut arr, 0);
+/// assert_eq!(arr[0], 4);
+/// assert!(is_heap(&arr));
+/// ```
+pub fn sift_down<T: Ord>(arr: &mut [T], index: usize) {
+    sift_down_by(arr, index, T::cmp)
+}
+
+/// move `arr[index]` down by `compare` until both children are not greater than it,
+/// the subtrees of `arr[index]` must be heaps by `compare`
+pub fn sift_down_by<T, F>(arr: &mut [T], mut index: usize, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    loop {
+        let mut child = 2 * index + 1;
+        if child >= len {
+            break;
+        }
+        if child + 1 < len && compare(&arr[child], &arr[child + 1]) == Ordering::Less {
+            child += 1;
+        }
+        if compare(&arr[index], &arr[child]) != Ordering::Less {
+            break;
+        }
+        counted::swap(arr, index, child);
+        index = child;
+    }
+}
+
+/// return true if `arr` is a max heap
+pub fn is_heap<T: Ord>(arr: &[T]) -> bool {
+    is_heap_by(arr, T::cmp)
+}
+
+/// return true if `arr` is a max heap by `compare`
+pub fn is_heap_by<T, F>(arr: &[T], mut compare: F) -> bool
+    where F: FnMut(&T, &T) -> Ordering
+{
+    (1..arr.len()).all(|i| compare(&arr[(i - 1) / 2], &arr[i]) != Ordering::Less)
+}
+
+#[test]
+fn test_heap_primitives() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let data_set: Vec<i32> = gen.uniform::<i32>(500).into_iter().map(|x| x % 100).collect();
+
+    // push one by one then pop all, like a priority queue
+    let mut arr = Vec::new();
+    for ele in data_set.iter() {
+        arr.push(*ele);
+        push_heap(&mut arr);
+        assert!(is_heap(&arr));
+    }
+    let mut popped = Vec::new();
+    while !arr.is_empty() {
+        pop_heap(&mut arr);
+        popped.push(arr.pop().unwrap());
+        assert!(is_heap(&arr));
+    }
+    let mut expected = data_set.clone();
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(popped, expected);
+
+    // min heap by reversed comparator
+    let mut arr = data_set.clone();
+    heapify_by(&mut arr, |a, b| b.cmp(a));
+    assert!(is_heap_by(&arr, |a: &i32, b: &i32| b.cmp(a)));
+    assert_eq!(arr[0], *data_set.iter().min().unwrap());
+    sort_heap_by(&mut arr, |a, b| b.cmp(a));
+    assert_eq!(arr, expected);
+
+    // decrease the top and sift it down
+    let mut arr = data_set.clone();
+    heapify(&mut arr);
+    arr[0] = -1000;
+    sift_down_by(&mut arr, 0, i32::cmp);
+    assert!(is_heap(&arr));
+
+    // change keys in the middle and restore the heap in place
+    let mut arr = data_set.clone();
+    heapify(&mut arr);
+    for i in (0..arr.len()).step_by(7) {
+        if i % 2 == 0 {
+            arr[i] += 50;
+            sift_up(&mut arr, i);
+        } else {
+            arr[i] -= 50;
+            sift_down(&mut arr, i);
+        }
+        assert!(is_heap(&arr));
+    }
+    sift_up(&mut arr, 0);
+    sift_down(&mut [0; 0], 0);
+    assert!(is_heap(&arr));
+}
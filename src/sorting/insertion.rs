@@ -1,3 +1,38 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::Sorter;
+
+/// insertion sort, insert every element into the sorted prefix before it
+/// 时间复杂度: O(n^2), 已排序的输入为O(n)
+/// 空间复杂度: O(1)
+pub struct InsertionSort;
+
+impl Sorter for InsertionSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = true;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort_by(arr, &mut compare)
+    }
+}
+
+/// insertion sort used by other algorithms to finish small partitions
+pub fn sort_by<T, F>(arr: &mut [T], compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    for i in 1..arr.len() {
+        let mut j = i;
+        while j > 0 && compare(&arr[j - 1], &arr[j]) == Ordering::Greater {
+            counted::swap(arr, j - 1, j);
+            j -= 1;
+        }
+    }
+}
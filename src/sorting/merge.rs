@@ -1,3 +1,403 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+use std::ptr;
+
+use crate::utils::counted;
+
+use super::{insertion, Sorter};
+
+/// runs not longer than this are sorted by insertion sort before merging in `in_place`
+const INSERTION_THRESHOLD: usize = 16;
+
+/// top-down merge sort, sort both halves recursively then merge them
+/// 时间复杂度: O(nlogn)
+/// 空间复杂度: O(n)
+pub struct MergeSort;
+
+impl Sorter for MergeSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = false;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort_by(arr, &mut compare)
+    }
+}
+
+/// bottom-up merge sort, merge runs of width 1, 2, 4... without recursion
+/// 时间复杂度: O(nlogn)
+/// 空间复杂度: O(n)
+pub struct BottomUpMergeSort;
+
+impl Sorter for BottomUpMergeSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = false;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        bottom_up(arr, &mut Vec::new(), compare)
+    }
+}
+
+/// natural merge sort, merge the ascending runs already in the input
+/// 时间复杂度: O(nlogr) r为输入中的run个数, 已排序的输入为O(n)
+/// 空间复杂度: O(n)
+pub struct NaturalMergeSort;
+
+impl Sorter for NaturalMergeSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = false;
+    const ADAPTIVE: bool = true;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        natural(arr, &mut Vec::new(), compare)
+    }
+}
+
+/// merge sort without buffer, runs are merged by rotation
+/// 时间复杂度: O(n(logn)^2)
+/// 空间复杂度: O(logn)
+pub struct InPlaceMergeSort;
+
+impl Sorter for InPlaceMergeSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        in_place(arr, compare)
+    }
+}
+
+/// merge sort used by other algorithms as stable O(nlogn) fallback
+pub fn sort_by<T, F>(arr: &mut [T], compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    top_down(arr, &mut Vec::new(), compare)
+}
+
+/// top-down merge sort with caller supplied scratch buffer
+/// only the spare capacity of `buf` is used, its elements are untouched,
+/// so the same buffer can be reused by many calls without allocating again
+/// ```
+/// use algorithms_rs::sorting::merge;
+///
+/// let mut buf = Vec::new();
+/// for _ in 0..3 {
+///     let mut arr = vec![5, 2, 4, 1, 3];
+///     merge::top_down(&mut arr, &mut buf, |a, b| a.cmp(b));
+///     assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+/// }
+/// ```
+pub fn top_down<T, F>(arr: &mut [T], buf: &mut Vec<T>, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let buf = scratch(buf, arr.len() / 2);
+    merge_sort(arr, buf, &mut compare);
+}
+
+/// bottom-up merge sort with caller supplied scratch buffer, see `top_down`
+pub fn bottom_up<T, F>(arr: &mut [T], buf: &mut Vec<T>, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    let buf = scratch(buf, len / 2);
+    let mut width = 1;
+    while width < len {
+        let mut start = 0;
+        while start + width < len {
+            let end = len.min(start + 2 * width);
+            unsafe { merge_with_buf(&mut arr[start..end], width, buf, &mut compare) }
+            start = end;
+        }
+        width *= 2;
+    }
+}
+
+/// natural merge sort with caller supplied scratch buffer, see `top_down`
+/// strictly descending runs are reversed, then neighbour runs are merged until one is left
+pub fn natural<T, F>(arr: &mut [T], buf: &mut Vec<T>, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    let buf = scratch(buf, len / 2);
+    // `runs[i]..runs[i + 1]` is a sorted run
+    let mut runs = vec![0];
+    let mut start = 0;
+    while start < len {
+        let end = start + find_run(&mut arr[start..], &mut compare);
+        runs.push(end);
+        start = end;
+    }
+    while runs.len() > 2 {
+        let mut merged = vec![0];
+        for pair in runs[1..].chunks(2) {
+            let start = *merged.last().unwrap();
+            if pair.len() == 2 {
+                unsafe { merge_with_buf(&mut arr[start..pair[1]], pair[0] - start, buf, &mut compare) }
+            }
+            merged.push(*pair.last().unwrap());
+        }
+        runs = merged;
+    }
+}
+
+/// return the length of the run at the start of `arr`, a strictly descending run is reversed
+pub(crate) fn find_run<T, F>(arr: &mut [T], compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len < 2 {
+        return len;
+    }
+    let mut end = 2;
+    if compare(&arr[1], &arr[0]) == Ordering::Less {
+        while end < len && compare(&arr[end], &arr[end - 1]) == Ordering::Less {
+            end += 1;
+        }
+        counted::reverse(&mut arr[..end]);
+    } else {
+        while end < len && compare(&arr[end], &arr[end - 1]) != Ordering::Less {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// merge sort without buffer, see `merge_in_place`
+/// ```
+/// use algorithms_rs::sorting::merge;
+///
+/// let mut arr = vec![5, 2, 4, 1, 3];
+/// merge::in_place(&mut arr, |a, b| a.cmp(b));
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+/// ```
+pub fn in_place<T, F>(arr: &mut [T], mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    in_place_sort(arr, &mut compare)
+}
+
+fn in_place_sort<T, F>(arr: &mut [T], compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len <= INSERTION_THRESHOLD {
+        insertion::sort_by(arr, compare);
+        return;
+    }
+    let mid = len / 2;
+    in_place_sort(&mut arr[..mid], compare);
+    in_place_sort(&mut arr[mid..], compare);
+    merge_rotate(arr, mid, compare);
+}
+
+/// merge sorted `left` and `right` into `out`, the merge is stable
+/// # Panics
+/// panics if `out.len() != left.len() + right.len()`
+/// ```
+/// use algorithms_rs::sorting::merge::merge;
+///
+/// let mut out = vec![0; 6];
+/// merge(&[1, 4, 6], &[2, 3, 5], &mut out, |a, b| a.cmp(b));
+/// assert_eq!(out, vec![1, 2, 3, 4, 5, 6]);
+/// ```
+pub fn merge<T, F>(left: &[T], right: &[T], out: &mut [T], mut compare: F)
+    where T: Clone,
+          F: FnMut(&T, &T) -> Ordering
+{
+    assert_eq!(out.len(), left.len() + right.len(), "output length must equal to the sum of inputs");
+    let (mut i, mut j) = (0, 0);
+    for slot in out.iter_mut() {
+        // take from the left on equal elements, keep merge stable
+        if j == right.len() || (i < left.len() && compare(&right[j], &left[i]) != Ordering::Less) {
+            *slot = left[i].clone();
+            i += 1;
+        } else {
+            *slot = right[j].clone();
+            j += 1;
+        }
+    }
+}
+
+/// merge sorted `arr[..mid]` and `arr[mid..]` without buffer,
+/// the longer run is cut in the middle, its counterpart in the other run is found by binary search
+/// and the two pieces between are swapped by rotation
+/// 时间复杂度: O(nlogn)
+/// 空间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::merge::merge_in_place;
+///
+/// let mut arr = vec![1, 4, 6, 2, 3, 5];
+/// merge_in_place(&mut arr, 3, |a, b| a.cmp(b));
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5, 6]);
+/// ```
+pub fn merge_in_place<T, F>(arr: &mut [T], mid: usize, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    assert!(mid <= arr.len(), "mid is out of bounds");
+    merge_rotate(arr, mid, &mut compare)
+}
+
+fn merge_rotate<T, F>(arr: &mut [T], mid: usize, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    if mid >= len - mid {
+        // pivot is the middle of left run, right elements less than it go before it
+        let cut = mid / 2;
+        let count = arr[mid..].partition_point(|x| compare(x, &arr[cut]) == Ordering::Less);
+        arr[cut..mid + count].rotate_left(mid - cut);
+        let pivot = cut + count;
+        let (before, after) = arr.split_at_mut(pivot);
+        merge_rotate(before, cut, compare);
+        merge_rotate(&mut after[1..], mid - cut - 1, compare);
+    } else {
+        // pivot is the middle of right run, left elements greater than it go after it
+        let cut = mid + (len - mid) / 2;
+        let count = arr[..mid].partition_point(|x| compare(&arr[cut], x) != Ordering::Less);
+        arr[count..=cut].rotate_left(mid - count);
+        let pivot = count + cut - mid;
+        let (before, after) = arr.split_at_mut(pivot);
+        merge_rotate(before, count, compare);
+        merge_rotate(&mut after[1..], mid - count, compare);
+    }
+}
+
+/// reserve room for `len` elements after the elements of `buf` and return pointer to it
+pub(crate) fn scratch<T>(buf: &mut Vec<T>, len: usize) -> *mut T {
+    buf.reserve(len);
+    unsafe { buf.as_mut_ptr().add(buf.len()) }
+}
+
+/// `buf` must have room for `arr.len() / 2` elements
+fn merge_sort<T, F>(arr: &mut [T], buf: *mut T, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len < 2 {
+        return;
+    }
+    let mid = len / 2;
+    merge_sort(&mut arr[..mid], buf, compare);
+    merge_sort(&mut arr[mid..], buf, compare);
+    unsafe { merge_with_buf(arr, mid, buf, compare) }
+}
+
+/// merge sorted `arr[..mid]` and `arr[mid..]` in place, the shorter run is moved into `buf` first
+/// so `buf` must have room for `min(mid, arr.len() - mid)` elements
+pub(crate) unsafe fn merge_with_buf<T, F>(arr: &mut [T], mid: usize, buf: *mut T, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    let arr = arr.as_mut_ptr();
+    if mid <= len - mid {
+        // merge forward, the left run is in `buf`
+        ptr::copy_nonoverlapping(arr, buf, mid);
+        // if `compare` panics the rest of the run in `buf` is copied back by the hole
+        let mut hole = MergeHole {
+            start: buf,
+            end: buf.add(mid),
+            dest: arr,
+        };
+        let mut right = arr.add(mid);
+        let right_end = arr.add(len);
+        while hole.start < hole.end && right < right_end {
+            // take from the left on equal elements, keep merge stable
+            let src = if compare(&*right, &*hole.start) == Ordering::Less {
+                let src = right;
+                right = right.add(1);
+                src
+            } else {
+                let src = hole.start;
+                hole.start = hole.start.add(1);
+                src
+            };
+            ptr::copy_nonoverlapping(src, hole.dest, 1);
+            hole.dest = hole.dest.add(1);
+        }
+    } else {
+        // merge backward, the right run is in `buf`
+        ptr::copy_nonoverlapping(arr.add(mid), buf, len - mid);
+        let mut hole = MergeHole {
+            start: buf,
+            end: buf.add(len - mid),
+            dest: arr.add(mid),
+        };
+        let mut out = arr.add(len);
+        while arr < hole.dest && hole.start < hole.end {
+            out = out.sub(1);
+            // take from the right on equal elements, keep merge stable
+            let src = if compare(&*hole.end.sub(1), &*hole.dest.sub(1)) == Ordering::Less {
+                hole.dest = hole.dest.sub(1);
+                hole.dest
+            } else {
+                hole.end = hole.end.sub(1);
+                hole.end
+            };
+            ptr::copy_nonoverlapping(src, out, 1);
+        }
+    }
+}
+
+/// elements in `start..end` are not placed yet, they are moved to `dest` when dropped
+pub(crate) struct MergeHole<T> {
+    pub(crate) start: *mut T,
+    pub(crate) end: *mut T,
+    pub(crate) dest: *mut T,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let len = self.end.offset_from(self.start) as usize;
+            ptr::copy_nonoverlapping(self.start, self.dest, len);
+        }
+    }
+}
+
+#[test]
+fn test_merge() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let mut buf = vec![(42, 0)];
+    for len in [0usize, 1, 2, 5, 33, 200].iter() {
+        let data_set: Vec<(u8, usize)> = gen.uniform::<u8>(*len).into_iter().map(|x| x % 8).zip(0..).collect();
+        for mid in [0, len / 3, len / 2, *len].iter() {
+            let (mut left, mut right) = (data_set[..*mid].to_vec(), data_set[*mid..].to_vec());
+            left.sort_by_key(|x| x.0);
+            right.sort_by_key(|x| x.0);
+            let mut expected = [left.clone(), right.clone()].concat();
+            expected.sort_by_key(|x| x.0);
+
+            let mut out = vec![(0, 0); *len];
+            merge(&left, &right, &mut out, |a, b| a.0.cmp(&b.0));
+            assert_eq!(out, expected);
+
+            let mut arr = [left.clone(), right.clone()].concat();
+            merge_in_place(&mut arr, *mid, |a, b| a.0.cmp(&b.0));
+            assert_eq!(arr, expected);
+
+            let mut arr = [left, right].concat();
+            let mut compare = |a: &(u8, usize), b: &(u8, usize)| a.0.cmp(&b.0);
+            unsafe { merge_with_buf(&mut arr, *mid, scratch(&mut buf, *len / 2), &mut compare) }
+            assert_eq!(arr, expected);
+        }
+    }
+    // the elements of scratch buffer are left as is
+    assert_eq!(buf, vec![(42, 0)]);
+}
@@ -1,6 +1,8 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
 //
+use std::cmp::Ordering;
+
 pub mod bubble;
 pub mod insertion;
 pub mod selection;
@@ -9,3 +11,209 @@ pub mod quickly;
 pub mod merge;
 pub mod heap;
 pub mod bucket;
+pub mod tim;
+pub mod select;
+
+/// common interface of sorting algorithms, every algorithm in this module implement it
+/// so they can be swapped with each other
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::bubble::BubbleSort;
+/// use algorithms_rs::sorting::heap::HeapSort;
+///
+/// fn sort_with<S: Sorter>(sorter: &S) -> Vec<i32> {
+///     let mut arr = vec![5, 2, 4, 1, 3];
+///     sorter.sort(&mut arr);
+///     arr
+/// }
+/// assert_eq!(sort_with(&BubbleSort), vec![1, 2, 3, 4, 5]);
+/// assert_eq!(sort_with(&HeapSort), vec![1, 2, 3, 4, 5]);
+/// ```
+pub trait Sorter {
+    /// equal elements keep their original order after sorting
+    const STABLE: bool;
+    /// only O(1) extra memory besides the recursion stack is used
+    const IN_PLACE: bool;
+    /// presorted input is sorted faster than random input
+    const ADAPTIVE: bool;
+
+    /// sort slice with given comparator function
+    fn sort_by<T, F>(&self, arr: &mut [T], compare: F)
+        where F: FnMut(&T, &T) -> Ordering;
+
+    /// sort slice in ascending order
+    fn sort<T: Ord>(&self, arr: &mut [T]) {
+        self.sort_by(arr, |a, b| a.cmp(b))
+    }
+
+    /// sort slice with given key extraction function
+    /// ```
+    /// use algorithms_rs::sorting::Sorter;
+    /// use algorithms_rs::sorting::insertion::InsertionSort;
+    ///
+    /// let mut arr = vec![-5i32, 2, -4, 1];
+    /// InsertionSort.sort_by_key(&mut arr, |x| x.abs());
+    /// assert_eq!(arr, vec![1, 2, -4, -5]);
+    /// ```
+    fn sort_by_key<T, K, F>(&self, arr: &mut [T], mut f: F)
+        where K: Ord,
+              F: FnMut(&T) -> K
+    {
+        self.sort_by(arr, |a, b| f(a).cmp(&f(b)))
+    }
+}
+
+/// return the metadata of sorter as `(stable, in_place, adaptive)`
+/// ```
+/// use algorithms_rs::sorting::properties;
+/// use algorithms_rs::sorting::merge::MergeSort;
+///
+/// assert_eq!(properties(&MergeSort), (true, false, false));
+/// ```
+pub fn properties<S: Sorter>(_sorter: &S) -> (bool, bool, bool) {
+    (S::STABLE, S::IN_PLACE, S::ADAPTIVE)
+}
+
+/// check sorter against `slice::sort` on randomized, already sorted, reverse sorted, all equal,
+/// duplicate heavy, nearly sorted, organ pipe, sawtooth and zipf distributed inputs,
+/// stability and adaptivity are checked when claimed
+#[cfg(test)]
+fn check_sorter<S: Sorter>(sorter: &S) {
+    use std::cell::Cell;
+
+    use crate::utils::gen::{self, Gen};
+
+    let mut gen = Gen::new(100);
+    for len in [0usize, 1, 2, 3, 10, 57, 300, 1000].iter() {
+        let len = *len;
+        let random: Vec<i64> = gen.uniform(len);
+        let mut sorted = random.clone();
+        sorted.sort();
+        let mut reverse = sorted.clone();
+        reverse.reverse();
+        let equal = vec![7i64; len];
+        let duplicate: Vec<i64> = random.iter().map(|x| x.rem_euclid(4)).collect();
+        let nearly: Vec<i64> = gen.nearly_sorted(len, len / 20);
+        let organ: Vec<i64> = gen::organ_pipe(len).into_iter().map(|x| x as i64).collect();
+        let sawtooth: Vec<i64> = gen::sawtooth(len, 7).into_iter().map(|x| x as i64).collect();
+        let zipf: Vec<i64> = gen.zipf(len, 50, 1.2).into_iter().map(|x| x as i64).collect();
+
+        let data_sets = [&random, &sorted, &reverse, &equal, &duplicate, &nearly, &organ, &sawtooth, &zipf];
+        for data_set in data_sets.iter() {
+            let mut expected = data_set.to_vec();
+            expected.sort();
+            let mut arr = data_set.to_vec();
+            sorter.sort(&mut arr);
+            assert_eq!(arr, expected);
+
+            expected.reverse();
+            let mut arr = data_set.to_vec();
+            sorter.sort_by(&mut arr, |a, b| b.cmp(a));
+            assert_eq!(arr, expected);
+
+            // pair every key with its original index, a stable sorter keeps the index order
+            let pairs: Vec<(i64, usize)> = data_set.iter()
+                .map(|x| x.rem_euclid(8))
+                .zip(0..)
+                .collect();
+            let mut expected = pairs.clone();
+            expected.sort_by_key(|p| p.0);
+            let mut arr = pairs.clone();
+            sorter.sort_by_key(&mut arr, |p| p.0);
+            if S::STABLE {
+                assert_eq!(arr, expected);
+            } else {
+                assert!(arr.windows(2).all(|w| w[0].0 <= w[1].0));
+            }
+        }
+
+        if S::ADAPTIVE && len >= 100 {
+            let count = |data_set: &[i64]| {
+                let compares = Cell::new(0);
+                sorter.sort_by(&mut data_set.to_vec(), |a, b| {
+                    compares.set(compares.get() + 1);
+                    a.cmp(b)
+                });
+                compares.get()
+            };
+            assert!(count(&sorted) < count(&random));
+        }
+    }
+}
+
+#[test]
+fn test_bubble_sort() {
+    check_sorter(&bubble::BubbleSort);
+}
+
+#[test]
+fn test_insertion_sort() {
+    use crate::utils::counted::{measure, Counted};
+
+    check_sorter(&insertion::InsertionSort);
+
+    // linear on sorted input, quadratic on reverse sorted input
+    let len = 1000;
+    let mut sorted: Vec<Counted<usize>> = (0..len).map(Counted).collect();
+    let ((), report) = measure(|| insertion::InsertionSort.sort(&mut sorted));
+    assert_eq!(report.comparisons, len as u64 - 1);
+    let mut reverse: Vec<Counted<usize>> = (0..len).rev().map(Counted).collect();
+    let ((), report) = measure(|| insertion::InsertionSort.sort(&mut reverse));
+    assert_eq!(report.comparisons, (len * (len - 1) / 2) as u64);
+    assert_eq!(report.clones, 0);
+    if cfg!(feature = "count-swaps") {
+        assert_eq!(report.swaps, (len * (len - 1) / 2) as u64);
+        let ((), report) = measure(|| insertion::InsertionSort.sort(&mut reverse));
+        assert_eq!(report.swaps, 0);
+    }
+}
+
+#[test]
+fn test_selection_sort() {
+    check_sorter(&selection::SelectionSort);
+}
+
+#[test]
+fn test_shell_sort() {
+    use shell::{Ciura, Hibbard, Knuth, Sedgewick, Shell, ShellSort, Tokuda};
+
+    check_sorter(&ShellSort::with_gaps(Shell));
+    check_sorter(&ShellSort::with_gaps(Hibbard));
+    check_sorter(&ShellSort::with_gaps(Knuth));
+    check_sorter(&ShellSort::with_gaps(Sedgewick));
+    check_sorter(&ShellSort::with_gaps(Tokuda));
+    check_sorter(&ShellSort::with_gaps(Ciura));
+}
+
+#[test]
+fn test_quick_sort() {
+    use quickly::{Partition, Pivot, QuickSort};
+
+    for pivot in [Pivot::First, Pivot::MedianOfThree, Pivot::Ninther, Pivot::Random].iter() {
+        check_sorter(&QuickSort::with_partition(*pivot, Partition::TwoWay));
+        check_sorter(&QuickSort::with_partition(*pivot, Partition::ThreeWay));
+    }
+}
+
+#[test]
+fn test_merge_sort() {
+    check_sorter(&merge::MergeSort);
+    check_sorter(&merge::BottomUpMergeSort);
+    check_sorter(&merge::NaturalMergeSort);
+    check_sorter(&merge::InPlaceMergeSort);
+}
+
+#[test]
+fn test_tim_sort() {
+    check_sorter(&tim::TimSort);
+}
+
+#[test]
+fn test_heap_sort() {
+    check_sorter(&heap::HeapSort);
+}
+
+#[test]
+fn test_bucket_sort() {
+    check_sorter(&bucket::BucketSort);
+}
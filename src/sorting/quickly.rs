@@ -1,3 +1,332 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use random::{Source, Xorshift128Plus};
+
+use crate::utils::counted;
+
+use super::{heap, insertion, Sorter};
+
+/// partitions not longer than this are finished by insertion sort
+const INSERTION_THRESHOLD: usize = 16;
+
+/// partitions not shorter than this use ninther instead of median of three
+const NINTHER_THRESHOLD: usize = 128;
+
+/// how the pivot of every partition is chosen
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Pivot {
+    /// the first element, quadratic on sorted input without the heap sort fallback
+    First,
+    /// median of the first, middle and last element
+    MedianOfThree,
+    /// median of three medians of three (Tukey's ninther), median of three on short partitions
+    Ninther,
+    /// a pseudo random element
+    Random,
+}
+
+/// how every partition is split around the pivot
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Partition {
+    /// hoare partition into `<= pivot` and `>= pivot`
+    TwoWay,
+    /// dutch national flag partition into `< pivot`, `== pivot` and `> pivot`,
+    /// elements equal to pivot are never touched again so inputs with many duplicates are fast
+    ThreeWay,
+}
+
+/// introsort, quick sort that finishes small partitions with insertion sort and
+/// switches to heap sort when the recursion gets deeper than 2*log2(n),
+/// so adversarial inputs can't make it quadratic
+/// 时间复杂度: O(nlogn)
+/// 空间复杂度: O(logn)
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::quickly::{Partition, Pivot, QuickSort};
+///
+/// let mut arr = vec![5, 2, 4, 1, 3];
+/// QuickSort::with_pivot(Pivot::Random).sort(&mut arr);
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+///
+/// let mut arr = vec![2, 1, 2, 1, 1, 2];
+/// QuickSort::with_partition(Pivot::Ninther, Partition::ThreeWay).sort(&mut arr);
+/// assert_eq!(arr, vec![1, 1, 1, 2, 2, 2]);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct QuickSort {
+    pub pivot: Pivot,
+    pub partition: Partition,
+}
+
+impl QuickSort {
+    /// create quick sort with ninther pivot and two way partition
+    pub fn new() -> Self {
+        Self::with_pivot(Pivot::Ninther)
+    }
+
+    /// create quick sort with given pivot strategy and two way partition
+    pub fn with_pivot(pivot: Pivot) -> Self {
+        Self::with_partition(pivot, Partition::TwoWay)
+    }
+
+    /// create quick sort with given pivot strategy and partition scheme
+    pub fn with_partition(pivot: Pivot, partition: Partition) -> Self {
+        Self { pivot, partition }
+    }
+}
+
+impl Default for QuickSort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sorter for QuickSort {
+    const STABLE: bool = false;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        let len = arr.len();
+        if len < 2 {
+            return;
+        }
+        let limit = 2 * (usize::BITS - len.leading_zeros()) as usize;
+        let mut rng = Xorshift128Plus::new([0x9E37_79B9_7F4A_7C15, len as u64]);
+        introsort(arr, self, limit, &mut rng, &mut compare)
+    }
+}
+
+fn introsort<T, F>(mut arr: &mut [T], sorter: &QuickSort, mut limit: usize, rng: &mut Xorshift128Plus, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    while arr.len() > INSERTION_THRESHOLD {
+        if limit == 0 {
+            heap::sort_by(arr, compare);
+            return;
+        }
+        limit -= 1;
+        let index = choose_pivot(arr, sorter.pivot, rng, compare);
+        counted::swap(arr, 0, index);
+        let equal = match sorter.partition {
+            Partition::TwoWay => {
+                let mid = partition(arr, compare);
+                mid..mid + 1
+            }
+            Partition::ThreeWay => partition3_in_place(arr, compare),
+        };
+        // recurse into the smaller part and loop on the larger one, the stack stays O(logn)
+        let (left, right) = arr.split_at_mut(equal.start);
+        let right = &mut right[equal.len()..];
+        if left.len() < right.len() {
+            introsort(left, sorter, limit, rng, compare);
+            arr = right;
+        } else {
+            introsort(right, sorter, limit, rng, compare);
+            arr = left;
+        }
+    }
+    insertion::sort_by(arr, compare);
+}
+
+/// return the index of pivot
+fn choose_pivot<T, F>(arr: &[T], pivot: Pivot, rng: &mut Xorshift128Plus, compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    let mid = len / 2;
+    match pivot {
+        Pivot::First => 0,
+        Pivot::Random => (rng.read_u64() % len as u64) as usize,
+        Pivot::Ninther if len >= NINTHER_THRESHOLD => {
+            let step = len / 8;
+            let a = median_of_three(arr, 0, step, 2 * step, compare);
+            let b = median_of_three(arr, mid - step, mid, mid + step, compare);
+            let c = median_of_three(arr, len - 1 - 2 * step, len - 1 - step, len - 1, compare);
+            median_of_three(arr, a, b, c, compare)
+        }
+        Pivot::Ninther | Pivot::MedianOfThree => median_of_three(arr, 0, mid, len - 1, compare),
+    }
+}
+
+pub(crate) fn median_of_three<T, F>(arr: &[T], a: usize, b: usize, c: usize, compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let less = |compare: &mut F, x: usize, y: usize| compare(&arr[x], &arr[y]) == Ordering::Less;
+    if less(compare, a, b) {
+        if less(compare, b, c) {
+            b
+        } else if less(compare, a, c) {
+            c
+        } else {
+            a
+        }
+    } else if less(compare, a, c) {
+        a
+    } else if less(compare, b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// hoare partition with `arr[0]` as pivot, return the final index of pivot
+/// both scans stop on elements equal to pivot, so duplicates are split evenly
+fn partition<T, F>(arr: &mut [T], compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let hi = arr.len() - 1;
+    let mut i = 0;
+    let mut j = arr.len();
+    loop {
+        i += 1;
+        while i < hi && compare(&arr[i], &arr[0]) == Ordering::Less {
+            i += 1;
+        }
+        j -= 1;
+        while j > 0 && compare(&arr[0], &arr[j]) == Ordering::Less {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        counted::swap(arr, i, j);
+    }
+    counted::swap(arr, 0, j);
+    j
+}
+
+/// dutch national flag partition with `arr[0]` as pivot, return the range equal to pivot
+/// the first element of the equal range is always the pivot, so it's used for comparing
+pub(crate) fn partition3_in_place<T, F>(arr: &mut [T], compare: &mut F) -> Range<usize>
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let mut lt = 0;
+    let mut i = 1;
+    let mut gt = arr.len();
+    while i < gt {
+        match compare(&arr[i], &arr[lt]) {
+            Ordering::Less => {
+                counted::swap(arr, lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                counted::swap(arr, i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    lt..gt
+}
+
+/// three way partition `arr` into elements less than, equal to and greater than pivot,
+/// return the range of elements equal to pivot
+/// ```
+/// use algorithms_rs::sorting::quickly::partition3;
+///
+/// let mut arr = vec![3, 1, 2, 3, 5, 2, 4, 2];
+/// let equal = partition3(&mut arr, &2);
+/// assert_eq!(equal, 1..4);
+/// assert_eq!(&arr[equal], &[2, 2, 2]);
+/// assert!(arr[..1].iter().all(|x| *x < 2));
+/// assert!(arr[4..].iter().all(|x| *x > 2));
+/// ```
+pub fn partition3<T: Ord>(arr: &mut [T], pivot: &T) -> Range<usize> {
+    partition3_by(arr, |ele| ele.cmp(pivot))
+}
+
+/// three way partition with a function comparing element to the pivot,
+/// like `slice::binary_search_by` the function returns the ordering of element against pivot
+pub fn partition3_by<T, F>(arr: &mut [T], mut compare: F) -> Range<usize>
+    where F: FnMut(&T) -> Ordering
+{
+    let mut lt = 0;
+    let mut i = 0;
+    let mut gt = arr.len();
+    while i < gt {
+        match compare(&arr[i]) {
+            Ordering::Less => {
+                counted::swap(arr, lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                counted::swap(arr, i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    lt..gt
+}
+
+#[test]
+fn test_partition3() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let data_set: Vec<u8> = gen.uniform::<u8>(500).into_iter().map(|x| x % 10).collect();
+    for pivot in 0..11 {
+        let mut arr = data_set.clone();
+        let equal = partition3(&mut arr, &pivot);
+        assert_eq!(equal.len(), data_set.iter().filter(|x| **x == pivot).count());
+        assert!(arr[..equal.start].iter().all(|x| *x < pivot));
+        assert!(arr[equal.clone()].iter().all(|x| *x == pivot));
+        assert!(arr[equal.end..].iter().all(|x| *x > pivot));
+    }
+    // select the k-th smallest element by partitioning only the side containing it
+    let mut arr = data_set.clone();
+    let k = 321;
+    let (mut lo, mut hi) = (0, arr.len());
+    let kth = loop {
+        let pivot = arr[lo];
+        let equal = partition3(&mut arr[lo..hi], &pivot);
+        if k < lo + equal.start {
+            hi = lo + equal.start;
+        } else if k >= lo + equal.end {
+            lo += equal.end;
+        } else {
+            break pivot;
+        }
+    };
+    let mut sorted = data_set.clone();
+    sorted.sort();
+    assert_eq!(kth, sorted[k]);
+}
+
+/// inputs made by McIlroy's "killer adversary" make every pivot as bad as possible,
+/// introsort must still stay O(nlogn)
+#[test]
+fn test_killer_adversary() {
+    use std::cell::Cell;
+
+    use crate::utils::gen::quicksort_killer;
+
+    let len: usize = 2000;
+    let bound = 4 * len * (usize::BITS - len.leading_zeros()) as usize;
+    let pivots = [Pivot::First, Pivot::MedianOfThree, Pivot::Ninther, Pivot::Random];
+    let sorters = pivots.iter().flat_map(|pivot| {
+        vec![
+            QuickSort::with_partition(*pivot, Partition::TwoWay),
+            QuickSort::with_partition(*pivot, Partition::ThreeWay),
+        ]
+    });
+    for sorter in sorters {
+        let mut arr = quicksort_killer(len, |arr, compare| sorter.sort_by(arr, compare));
+        let compares = Cell::new(0);
+        sorter.sort_by(&mut arr, |x, y| {
+            compares.set(compares.get() + 1);
+            x.cmp(y)
+        });
+        assert!(arr.windows(2).all(|w| w[0] <= w[1]));
+        assert!(compares.get() < bound, "{:?} used {} compares", sorter, compares.get());
+    }
+}
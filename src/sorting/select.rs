@@ -0,0 +1,250 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/16 16:40
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::{heap, insertion};
+use super::quickly::{median_of_three, partition3_in_place};
+
+/// partitions not longer than this are finished by insertion sort
+const INSERTION_THRESHOLD: usize = 16;
+
+/// sort the `k` smallest elements into `arr[..k]`, the order of the rest is unspecified
+/// a max heap of the `k` smallest elements seen so far is kept in `arr[..k]`
+/// 时间复杂度: O(nlogk)
+/// 空间复杂度: O(1)
+/// ```
+/// use algorithms_rs::sorting::select::partial_sort;
+///
+/// let mut arr = vec![9, 4, 7, 1, 8, 2, 6];
+/// partial_sort(&mut arr, 3);
+/// assert_eq!(&arr[..3], &[1, 2, 4]);
+/// ```
+pub fn partial_sort<T: Ord>(arr: &mut [T], k: usize) {
+    partial_sort_by(arr, k, T::cmp)
+}
+
+/// sort the `k` smallest elements by `compare` into `arr[..k]`, see `partial_sort`
+pub fn partial_sort_by<T, F>(arr: &mut [T], k: usize, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let k = k.min(arr.len());
+    if k == 0 {
+        return;
+    }
+    heap::heapify_by(&mut arr[..k], &mut compare);
+    for i in k..arr.len() {
+        if compare(&arr[i], &arr[0]) == Ordering::Less {
+            counted::swap(arr, 0, i);
+            heap::sift_down_by(&mut arr[..k], 0, &mut compare);
+        }
+    }
+    heap::sort_heap_by(&mut arr[..k], compare);
+}
+
+/// rearrange `arr` so that `arr[n]` is the element at `n` if `arr` were sorted,
+/// no element before it is greater and no element after it is less
+/// quickselect with median of three pivot, once a partition keeps more than 3/4 of its range
+/// every following pivot is chosen by median of medians. the ranges before that shrink
+/// geometrically and the one bad partition costs O(n), so the worst case is still linear
+/// 时间复杂度: O(n)
+/// 空间复杂度: O(logn)
+/// # Panics
+/// panics if `n >= arr.len()`
+/// ```
+/// use algorithms_rs::sorting::select::nth_element;
+///
+/// let mut arr = vec![9, 4, 7, 1, 8, 2, 6];
+/// nth_element(&mut arr, 3);
+/// assert_eq!(arr[3], 6);
+/// assert!(arr[..3].iter().all(|x| *x <= 6));
+/// assert!(arr[4..].iter().all(|x| *x >= 6));
+/// ```
+pub fn nth_element<T: Ord>(arr: &mut [T], n: usize) {
+    nth_element_by(arr, n, T::cmp)
+}
+
+/// rearrange `arr` by `compare` so that `arr[n]` is the element at `n` if `arr` were sorted,
+/// see `nth_element`
+pub fn nth_element_by<T, F>(arr: &mut [T], n: usize, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    assert!(n < arr.len(), "index {} is out of bounds of length {}", n, arr.len());
+    select(arr, n, &mut compare)
+}
+
+fn select<T, F>(mut arr: &mut [T], mut n: usize, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    // set after the first partition which doesn't shrink the range to 3/4
+    let mut fallback = false;
+    loop {
+        let len = arr.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion::sort_by(arr, compare);
+            return;
+        }
+        let pivot = if fallback {
+            median_of_medians(arr, compare)
+        } else {
+            median_of_three(arr, 0, len / 2, len - 1, compare)
+        };
+        counted::swap(arr, 0, pivot);
+        let equal = partition3_in_place(arr, compare);
+        if n < equal.start {
+            arr = &mut std::mem::take(&mut arr)[..equal.start];
+        } else if n >= equal.end {
+            n -= equal.end;
+            arr = &mut std::mem::take(&mut arr)[equal.end..];
+        } else {
+            return;
+        }
+        fallback = fallback || arr.len() > len / 4 * 3;
+    }
+}
+
+/// move the median of every group of 5 to the front, return the index of median of these medians
+/// it's greater than at least 3/10 of the elements and less than another 3/10
+fn median_of_medians<T, F>(arr: &mut [T], compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let groups = arr.len() / 5;
+    for i in 0..groups {
+        insertion::sort_by(&mut arr[i * 5..i * 5 + 5], compare);
+        counted::swap(arr, i, i * 5 + 2);
+    }
+    select(&mut arr[..groups], groups / 2, compare);
+    groups / 2
+}
+
+/// return the `k` greatest elements of `iter` in descending order,
+/// only a min heap of `k` elements is kept, so `iter` can be a stream of any length
+/// 时间复杂度: O(nlogk)
+/// 空间复杂度: O(k)
+/// ```
+/// use algorithms_rs::sorting::select::top_k;
+///
+/// assert_eq!(top_k(vec![9, 4, 7, 1, 8, 2, 6], 3), vec![9, 8, 7]);
+/// assert_eq!(top_k((0..1_000_000).map(|x| x % 1000), 2), vec![999, 999]);
+/// ```
+pub fn top_k<I>(iter: I, k: usize) -> Vec<I::Item>
+    where I: IntoIterator,
+          I::Item: Ord
+{
+    top_k_by(iter, k, Ord::cmp)
+}
+
+/// return the `k` greatest elements of `iter` by `compare` in descending order, see `top_k`
+/// ```
+/// use algorithms_rs::sorting::select::top_k_by;
+///
+/// let scores = vec![("a", 0.5), ("b", 0.9), ("c", 0.1), ("d", 0.7)];
+/// let best = top_k_by(scores, 2, |x, y| x.1.partial_cmp(&y.1).unwrap());
+/// assert_eq!(best, vec![("b", 0.9), ("d", 0.7)]);
+/// ```
+pub fn top_k_by<I, F>(iter: I, k: usize, mut compare: F) -> Vec<I::Item>
+    where I: IntoIterator,
+          F: FnMut(&I::Item, &I::Item) -> Ordering
+{
+    let mut reversed = |a: &I::Item, b: &I::Item| compare(b, a);
+    let mut heap = Vec::with_capacity(k);
+    if k == 0 {
+        return heap;
+    }
+    for ele in iter {
+        if heap.len() < k {
+            heap.push(ele);
+            heap::push_heap_by(&mut heap, &mut reversed);
+        } else if reversed(&ele, &heap[0]) == Ordering::Less {
+            heap[0] = ele;
+            heap::sift_down_by(&mut heap, 0, &mut reversed);
+        }
+    }
+    heap::sort_heap_by(&mut heap, reversed);
+    heap
+}
+
+/// return the `k` greatest elements of `iter` by key in descending order, see `top_k`
+pub fn top_k_by_key<I, K, F>(iter: I, k: usize, mut key: F) -> Vec<I::Item>
+    where I: IntoIterator,
+          K: Ord,
+          F: FnMut(&I::Item) -> K
+{
+    top_k_by(iter, k, |a, b| key(a).cmp(&key(b)))
+}
+
+#[test]
+fn test_select() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    for len in [1usize, 2, 16, 17, 100, 1000].iter() {
+        let data_set: Vec<i32> = gen.uniform::<i32>(*len).into_iter().map(|x| x % 200).collect();
+        let mut sorted = data_set.clone();
+        sorted.sort();
+        for k in [0, 1, len / 3, len - 1, *len].iter() {
+            let mut arr = data_set.clone();
+            partial_sort(&mut arr, *k);
+            assert_eq!(&arr[..*k], &sorted[..*k]);
+
+            let mut descending = sorted[len - k..].to_vec();
+            descending.reverse();
+            assert_eq!(top_k(data_set.iter().copied(), *k), descending);
+
+            if k < len {
+                let mut arr = data_set.clone();
+                nth_element(&mut arr, *k);
+                assert_eq!(arr[*k], sorted[*k]);
+                assert!(arr[..*k].iter().all(|x| *x <= sorted[*k]));
+                assert!(arr[*k..].iter().all(|x| *x >= sorted[*k]));
+            }
+        }
+    }
+}
+
+/// median of medians must keep the selection linear even if every quickselect pivot is bad
+#[test]
+fn test_median_of_medians() {
+    use std::cell::Cell;
+
+    let len = 10000;
+    let compares = Cell::new(0);
+    // sawtooth with median of three always picking a small pivot
+    let data_set: Vec<usize> = (0..len).map(|i| if i % 2 == 0 { i } else { len - i }).collect();
+    let mut sorted = data_set.clone();
+    sorted.sort();
+    let mut arr = data_set.clone();
+    nth_element_by(&mut arr, len / 2, |a, b| {
+        compares.set(compares.get() + 1);
+        a.cmp(b)
+    });
+    assert_eq!(arr[len / 2], sorted[len / 2]);
+    assert!(compares.get() < 30 * len, "used {} compares", compares.get());
+
+    let mut arr = data_set.clone();
+    let groups = median_of_medians(&mut arr, &mut usize::cmp);
+    let pivot = arr[groups];
+    assert!(data_set.iter().filter(|x| **x < pivot).count() >= len * 3 / 10 - 5);
+    assert!(data_set.iter().filter(|x| **x > pivot).count() >= len * 3 / 10 - 5);
+}
+
+/// inputs made by McIlroy's "killer adversary" make every median of three pivot as bad as
+/// possible, the selection must still take linear comparisons
+#[test]
+fn test_select_killer_adversary() {
+    use crate::utils::counted::{measure, Counted};
+    use crate::utils::gen::quicksort_killer;
+
+    for len in [1000usize, 10000, 100000].iter() {
+        let n = len / 2;
+        let data_set = quicksort_killer(*len, |arr, compare| nth_element_by(arr, n, |x, y| compare(x, y)));
+        let mut arr: Vec<Counted<usize>> = data_set.iter().copied().map(Counted).collect();
+        let ((), report) = measure(|| nth_element(&mut arr, n));
+        let mut sorted = data_set;
+        sorted.sort();
+        assert_eq!(arr[n].0, sorted[n]);
+        assert!(report.comparisons <= 10 * *len as u64, "{} used {} compares", len, report.comparisons);
+    }
+}
@@ -1,3 +1,33 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::Sorter;
+
+/// selection sort, select the minimum of the unsorted part and put it at the front
+/// 时间复杂度: O(n^2)
+/// 空间复杂度: O(1)
+pub struct SelectionSort;
+
+impl Sorter for SelectionSort {
+    const STABLE: bool = false;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = false;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        for i in 0..arr.len() {
+            let mut min = i;
+            for j in i + 1..arr.len() {
+                if compare(&arr[j], &arr[min]) == Ordering::Less {
+                    min = j;
+                }
+            }
+            counted::swap(arr, i, min);
+        }
+    }
+}
@@ -1,3 +1,204 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 17:53
-//
\ No newline at end of file
+//
+use std::cmp::Ordering;
+
+use crate::utils::counted;
+
+use super::Sorter;
+
+/// gap sequence of shell sort, implement it to try own sequence
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::shell::{GapSequence, ShellSort};
+///
+/// /// powers of 5
+/// struct Five;
+///
+/// impl GapSequence for Five {
+///     fn gaps(&self, len: usize) -> Vec<usize> {
+///         let mut gaps: Vec<usize> = (0..).map(|k| 5usize.pow(k)).take_while(|gap| *gap < len).collect();
+///         gaps.reverse();
+///         gaps
+///     }
+/// }
+///
+/// let mut arr = vec![5, 2, 4, 1, 3];
+/// ShellSort::with_gaps(Five).sort(&mut arr);
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+/// ```
+pub trait GapSequence {
+    /// return gaps used for input of length `len` in descending order, the last gap must be 1
+    fn gaps(&self, len: usize) -> Vec<usize>;
+}
+
+/// take terms of increasing sequence while they are less than `len` and reverse them,
+/// the sequence must start with 1
+fn below<I>(len: usize, terms: I) -> Vec<usize>
+    where I: Iterator<Item=usize>
+{
+    let mut gaps: Vec<usize> = terms.take_while(|gap| *gap < len.max(2)).collect();
+    gaps.reverse();
+    gaps
+}
+
+/// Shell 1959: n/2, n/4, ..., 1
+/// 最坏时间复杂度: O(n^2)
+pub struct Shell;
+
+impl GapSequence for Shell {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        let mut gaps = Vec::new();
+        let mut gap = len / 2;
+        while gap > 0 {
+            gaps.push(gap);
+            gap /= 2;
+        }
+        if gaps.is_empty() {
+            gaps.push(1);
+        }
+        gaps
+    }
+}
+
+/// Hibbard 1963: 2^k - 1
+/// 最坏时间复杂度: O(n^(3/2))
+pub struct Hibbard;
+
+impl GapSequence for Hibbard {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        below(len, (1..usize::BITS).map(|k| (1 << k) - 1))
+    }
+}
+
+/// Knuth 1973: (3^k - 1) / 2 not greater than n/3
+/// 最坏时间复杂度: O(n^(3/2))
+pub struct Knuth;
+
+impl GapSequence for Knuth {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        below(len / 3 + 1, (1..).map(|k| (3usize.pow(k) - 1) / 2).take(39))
+    }
+}
+
+/// Sedgewick 1986: 1, 4^k + 3 * 2^(k-1) + 1
+/// 最坏时间复杂度: O(n^(4/3))
+pub struct Sedgewick;
+
+impl GapSequence for Sedgewick {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        let terms = (1..31).map(|k| 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1);
+        below(len, std::iter::once(1).chain(terms))
+    }
+}
+
+/// Tokuda 1992: ceil((9 * (9/4)^(k-1) - 4) / 5)
+pub struct Tokuda;
+
+impl GapSequence for Tokuda {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        below(len, (0..50).map(|k| ((9.0 * 2.25f64.powi(k) - 4.0) / 5.0).ceil() as usize))
+    }
+}
+
+/// Ciura 2001: experimentally found 1, 4, 10, 23, 57, 132, 301, 701, 1750,
+/// extended by multiplying 2.25 for longer inputs
+pub struct Ciura;
+
+impl GapSequence for Ciura {
+    fn gaps(&self, len: usize) -> Vec<usize> {
+        const GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+        let extended = (1..40).map(|k| (1750.0 * 2.25f64.powi(k)) as usize);
+        below(len, GAPS.iter().copied().chain(extended))
+    }
+}
+
+/// shell sort, insertion sort over elements `gap` apart with a shrinking gap
+/// 时间复杂度: 取决于gap序列, 见各序列的说明
+/// 空间复杂度: O(1)
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::shell::{Knuth, ShellSort};
+///
+/// let mut arr = vec![5, 2, 4, 1, 3];
+/// ShellSort::with_gaps(Knuth).sort(&mut arr);
+/// assert_eq!(arr, vec![1, 2, 3, 4, 5]);
+/// ```
+pub struct ShellSort<G = Ciura> {
+    pub gaps: G,
+}
+
+impl ShellSort {
+    /// create shell sort with ciura gap sequence
+    pub fn new() -> Self {
+        Self::with_gaps(Ciura)
+    }
+}
+
+impl Default for ShellSort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: GapSequence> ShellSort<G> {
+    /// create shell sort with given gap sequence
+    pub fn with_gaps(gaps: G) -> Self {
+        Self { gaps }
+    }
+}
+
+impl<G: GapSequence> Sorter for ShellSort<G> {
+    const STABLE: bool = false;
+    const IN_PLACE: bool = true;
+    const ADAPTIVE: bool = true;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        for gap in self.gaps.gaps(arr.len()) {
+            for i in gap..arr.len() {
+                let mut j = i;
+                while j >= gap && compare(&arr[j - gap], &arr[j]) == Ordering::Greater {
+                    counted::swap(arr, j - gap, j);
+                    j -= gap;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_gap_sequences() {
+    assert_eq!(Shell.gaps(20), vec![10, 5, 2, 1]);
+    assert_eq!(Hibbard.gaps(20), vec![15, 7, 3, 1]);
+    assert_eq!(Knuth.gaps(100), vec![13, 4, 1]);
+    assert_eq!(Sedgewick.gaps(300), vec![281, 77, 23, 8, 1]);
+    assert_eq!(Tokuda.gaps(300), vec![233, 103, 46, 20, 9, 4, 1]);
+    assert_eq!(Ciura.gaps(5000), vec![3937, 1750, 701, 301, 132, 57, 23, 10, 4, 1]);
+    for len in 0..3 {
+        assert_eq!(Shell.gaps(len), vec![1]);
+        assert_eq!(Hibbard.gaps(len), vec![1]);
+        assert_eq!(Knuth.gaps(len), vec![1]);
+        assert_eq!(Sedgewick.gaps(len), vec![1]);
+        assert_eq!(Tokuda.gaps(len), vec![1]);
+        assert_eq!(Ciura.gaps(len), vec![1]);
+    }
+}
+
+#[test]
+fn bench_gap_sequences() {
+    use crate::utils::bench::Bench;
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    let data_set: Vec<i32> = gen.uniform(10000);
+    let bench = Bench::new();
+    let setup = || data_set.clone();
+    println!("shell: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Shell).sort(&mut arr)));
+    println!("hibbard: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Hibbard).sort(&mut arr)));
+    println!("knuth: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Knuth).sort(&mut arr)));
+    println!("sedgewick: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Sedgewick).sort(&mut arr)));
+    println!("tokuda: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Tokuda).sort(&mut arr)));
+    println!("ciura: {}", bench.run_with_setup(setup, |mut arr| ShellSort::with_gaps(Ciura).sort(&mut arr)));
+}
@@ -0,0 +1,419 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/16 15:02
+//
+use std::cmp::Ordering;
+use std::ptr;
+use std::slice;
+
+use super::merge::{find_run, scratch, MergeHole};
+use super::Sorter;
+
+/// inputs shorter than this are sorted by binary insertion sort only
+const MIN_MERGE: usize = 64;
+
+/// initial number of consecutive wins of one run before switching to galloping mode
+const MIN_GALLOP: usize = 7;
+
+/// timsort, find the natural runs of the input, extend short runs to `min_run` by binary insertion
+/// sort, and merge the runs on a stack whose lengths are kept like the fibonacci numbers,
+/// when one run keeps winning the merge switches to galloping mode and copies whole blocks
+/// 时间复杂度: O(nlogn), 已排序的输入为O(n)
+/// 空间复杂度: O(n)
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::tim::TimSort;
+///
+/// let mut arr: Vec<i32> = (0..100).chain(50..70).chain((0..30).rev()).collect();
+/// let mut expected = arr.clone();
+/// expected.sort();
+/// TimSort.sort(&mut arr);
+/// assert_eq!(arr, expected);
+/// ```
+pub struct TimSort;
+
+impl Sorter for TimSort {
+    const STABLE: bool = true;
+    const IN_PLACE: bool = false;
+    const ADAPTIVE: bool = true;
+
+    fn sort_by<T, F>(&self, arr: &mut [T], compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort(arr, &mut Vec::new(), compare)
+    }
+}
+
+/// timsort with caller supplied scratch buffer, see `merge::top_down`
+pub fn sort<T, F>(arr: &mut [T], buf: &mut Vec<T>, mut compare: F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    let len = arr.len();
+    if len < 2 {
+        return;
+    }
+    if len < MIN_MERGE {
+        let run = find_run(arr, &mut compare);
+        binary_insertion_sort(arr, run, &mut compare);
+        return;
+    }
+    let mut state = TimState {
+        buf: scratch(buf, len / 2),
+        runs: Vec::new(),
+        min_gallop: MIN_GALLOP,
+    };
+    let min_run = min_run(len);
+    let mut start = 0;
+    while start < len {
+        let mut run = find_run(&mut arr[start..], &mut compare);
+        if run < min_run {
+            let force = min_run.min(len - start);
+            binary_insertion_sort(&mut arr[start..start + force], run, &mut compare);
+            run = force;
+        }
+        state.runs.push(Run { start, len: run });
+        state.merge_collapse(arr, &mut compare);
+        start += run;
+    }
+    state.merge_force_collapse(arr, &mut compare);
+}
+
+/// return the minimum run length, `len / min_run` is a power of 2 or a bit less than it
+/// so the final merges are balanced
+fn min_run(mut len: usize) -> usize {
+    let mut r = 0;
+    while len >= MIN_MERGE {
+        r |= len & 1;
+        len >>= 1;
+    }
+    len + r
+}
+
+/// `arr[..sorted]` is already sorted, insert the rest one by one at the position found by binary search
+fn binary_insertion_sort<T, F>(arr: &mut [T], sorted: usize, compare: &mut F)
+    where F: FnMut(&T, &T) -> Ordering
+{
+    for i in sorted.max(1)..arr.len() {
+        // insert after equal elements, keep sort stable
+        let pos = arr[..i].partition_point(|x| compare(&arr[i], x) != Ordering::Less);
+        arr[pos..=i].rotate_right(1);
+    }
+}
+
+/// return the partition point of `arr` for `pred`, the search starts from `hint` with exponential
+/// growing steps, so it's fast when the partition point is close to `hint`
+fn gallop<T, P>(arr: &[T], hint: usize, mut pred: P) -> usize
+    where P: FnMut(&T) -> bool
+{
+    let len = arr.len();
+    if len == 0 {
+        return 0;
+    }
+    let (lo, hi) = if pred(&arr[hint]) {
+        let (mut last, mut offset) = (hint, 1);
+        while hint + offset < len && pred(&arr[hint + offset]) {
+            last = hint + offset;
+            offset = offset * 2 + 1;
+        }
+        (last + 1, len.min(hint + offset))
+    } else {
+        let (mut last, mut offset) = (hint, 1);
+        while offset <= hint && !pred(&arr[hint - offset]) {
+            last = hint - offset;
+            offset = offset * 2 + 1;
+        }
+        (if offset <= hint { hint - offset + 1 } else { 0 }, last)
+    };
+    lo + arr[lo..hi].partition_point(pred)
+}
+
+/// return the index of first element not less than `key`
+fn gallop_left<T, F>(key: &T, arr: &[T], hint: usize, compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    gallop(arr, hint, |x| compare(x, key) == Ordering::Less)
+}
+
+/// return the index of first element greater than `key`
+fn gallop_right<T, F>(key: &T, arr: &[T], hint: usize, compare: &mut F) -> usize
+    where F: FnMut(&T, &T) -> Ordering
+{
+    gallop(arr, hint, |x| compare(key, x) != Ordering::Less)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+struct TimState<T> {
+    /// room for `len / 2` elements
+    buf: *mut T,
+    runs: Vec<Run>,
+    min_gallop: usize,
+}
+
+impl<T> TimState<T> {
+    /// merge runs until the invariants hold for the top of stack:
+    /// `runs[n - 2] > runs[n - 1] + runs[n]` and `runs[n - 1] > runs[n]`
+    fn merge_collapse<F>(&mut self, arr: &mut [T], compare: &mut F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        while self.runs.len() > 1 {
+            let runs = &self.runs;
+            let mut n = runs.len() - 2;
+            if (n > 0 && runs[n - 1].len <= runs[n].len + runs[n + 1].len)
+                || (n > 1 && runs[n - 2].len <= runs[n - 1].len + runs[n].len) {
+                if runs[n - 1].len < runs[n + 1].len {
+                    n -= 1;
+                }
+            } else if runs[n].len > runs[n + 1].len {
+                break;
+            }
+            self.merge_at(arr, n, compare);
+        }
+    }
+
+    /// merge all runs left on the stack
+    fn merge_force_collapse<F>(&mut self, arr: &mut [T], compare: &mut F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            if n > 0 && self.runs[n - 1].len < self.runs[n + 1].len {
+                n -= 1;
+            }
+            self.merge_at(arr, n, compare);
+        }
+    }
+
+    /// merge `runs[i]` and `runs[i + 1]`
+    fn merge_at<F>(&mut self, arr: &mut [T], i: usize, compare: &mut F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        let (left, right) = (self.runs[i], self.runs[i + 1]);
+        self.runs[i].len += right.len;
+        self.runs.remove(i + 1);
+
+        let arr = &mut arr[left.start..right.start + right.len];
+        let mid = left.len;
+        // elements of the left run not greater than the first of right run are in place already
+        let skip = gallop_right(&arr[mid], &arr[..mid], 0, compare);
+        if skip == mid {
+            return;
+        }
+        // and so are elements of the right run not less than the last of left run
+        let keep = gallop_left(&arr[mid - 1], &arr[mid..], right.len - 1, compare);
+        let arr = &mut arr[skip..mid + keep];
+        let mid = mid - skip;
+        unsafe {
+            if mid <= keep {
+                self.merge_lo(arr, mid, compare)
+            } else {
+                self.merge_hi(arr, mid, compare)
+            }
+        }
+    }
+
+    /// merge forward with the left run in buffer
+    unsafe fn merge_lo<F>(&mut self, arr: &mut [T], mid: usize, compare: &mut F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        let len = arr.len();
+        let arr = arr.as_mut_ptr();
+        ptr::copy_nonoverlapping(arr, self.buf, mid);
+        // `hole.dest + (hole.end - hole.start) == right` all the time,
+        // so if `compare` panics the rest of buffer fills the gap exactly
+        let mut hole = MergeHole {
+            start: self.buf,
+            end: self.buf.add(mid),
+            dest: arr,
+        };
+        let mut right = arr.add(mid);
+        let right_end = arr.add(len);
+        let mut min_gallop = self.min_gallop;
+        'outer: loop {
+            let (mut left_wins, mut right_wins) = (0, 0);
+            // one pair at a time until a run wins `min_gallop` times in a row
+            loop {
+                if hole.start == hole.end || right == right_end {
+                    break 'outer;
+                }
+                if compare(&*right, &*hole.start) == Ordering::Less {
+                    ptr::copy_nonoverlapping(right, hole.dest, 1);
+                    right = right.add(1);
+                    right_wins += 1;
+                    left_wins = 0;
+                } else {
+                    ptr::copy_nonoverlapping(hole.start, hole.dest, 1);
+                    hole.start = hole.start.add(1);
+                    left_wins += 1;
+                    right_wins = 0;
+                }
+                hole.dest = hole.dest.add(1);
+                if hole.start == hole.end || right == right_end {
+                    break 'outer;
+                }
+                if left_wins >= min_gallop || right_wins >= min_gallop {
+                    break;
+                }
+            }
+            // galloping until both runs win less than `MIN_GALLOP` at a time
+            loop {
+                let left = slice::from_raw_parts(hole.start, hole.end.offset_from(hole.start) as usize);
+                left_wins = gallop_right(&*right, left, 0, compare);
+                ptr::copy_nonoverlapping(hole.start, hole.dest, left_wins);
+                hole.start = hole.start.add(left_wins);
+                hole.dest = hole.dest.add(left_wins);
+                if hole.start == hole.end {
+                    break 'outer;
+                }
+                ptr::copy_nonoverlapping(right, hole.dest, 1);
+                right = right.add(1);
+                hole.dest = hole.dest.add(1);
+                if right == right_end {
+                    break 'outer;
+                }
+
+                let rest = slice::from_raw_parts(right, right_end.offset_from(right) as usize);
+                right_wins = gallop_left(&*hole.start, rest, 0, compare);
+                ptr::copy(right, hole.dest, right_wins);
+                right = right.add(right_wins);
+                hole.dest = hole.dest.add(right_wins);
+                if right == right_end {
+                    break 'outer;
+                }
+                ptr::copy_nonoverlapping(hole.start, hole.dest, 1);
+                hole.start = hole.start.add(1);
+                hole.dest = hole.dest.add(1);
+                if hole.start == hole.end {
+                    break 'outer;
+                }
+
+                min_gallop = min_gallop.saturating_sub(1);
+                if left_wins < MIN_GALLOP && right_wins < MIN_GALLOP {
+                    break;
+                }
+            }
+            // penalize leaving galloping mode
+            min_gallop += 2;
+        }
+        self.min_gallop = min_gallop.max(1);
+    }
+
+    /// merge backward with the right run in buffer
+    unsafe fn merge_hi<F>(&mut self, arr: &mut [T], mid: usize, compare: &mut F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        let len = arr.len();
+        let arr = arr.as_mut_ptr();
+        ptr::copy_nonoverlapping(arr.add(mid), self.buf, len - mid);
+        // the rest of left run is `arr..hole.dest`, `out == hole.dest + (hole.end - hole.start)`
+        let mut hole = MergeHole {
+            start: self.buf,
+            end: self.buf.add(len - mid),
+            dest: arr.add(mid),
+        };
+        let mut out = arr.add(len);
+        let mut min_gallop = self.min_gallop;
+        'outer: loop {
+            let (mut left_wins, mut right_wins) = (0, 0);
+            loop {
+                if hole.start == hole.end || hole.dest == arr {
+                    break 'outer;
+                }
+                out = out.sub(1);
+                // take from the right on equal elements, keep merge stable
+                if compare(&*hole.end.sub(1), &*hole.dest.sub(1)) == Ordering::Less {
+                    hole.dest = hole.dest.sub(1);
+                    ptr::copy_nonoverlapping(hole.dest, out, 1);
+                    left_wins += 1;
+                    right_wins = 0;
+                } else {
+                    hole.end = hole.end.sub(1);
+                    ptr::copy_nonoverlapping(hole.end, out, 1);
+                    right_wins += 1;
+                    left_wins = 0;
+                }
+                if hole.start == hole.end || hole.dest == arr {
+                    break 'outer;
+                }
+                if left_wins >= min_gallop || right_wins >= min_gallop {
+                    break;
+                }
+            }
+            loop {
+                let left_len = hole.dest.offset_from(arr) as usize;
+                let left = slice::from_raw_parts(arr, left_len);
+                left_wins = left_len - gallop_right(&*hole.end.sub(1), left, left_len - 1, compare);
+                out = out.sub(left_wins);
+                hole.dest = hole.dest.sub(left_wins);
+                ptr::copy(hole.dest, out, left_wins);
+                if hole.dest == arr {
+                    break 'outer;
+                }
+                out = out.sub(1);
+                hole.end = hole.end.sub(1);
+                ptr::copy_nonoverlapping(hole.end, out, 1);
+                if hole.start == hole.end {
+                    break 'outer;
+                }
+
+                let right_len = hole.end.offset_from(hole.start) as usize;
+                let right = slice::from_raw_parts(hole.start, right_len);
+                right_wins = right_len - gallop_left(&*hole.dest.sub(1), right, right_len - 1, compare);
+                out = out.sub(right_wins);
+                hole.end = hole.end.sub(right_wins);
+                ptr::copy_nonoverlapping(hole.end, out, right_wins);
+                if hole.start == hole.end {
+                    break 'outer;
+                }
+                out = out.sub(1);
+                hole.dest = hole.dest.sub(1);
+                ptr::copy_nonoverlapping(hole.dest, out, 1);
+                if hole.dest == arr {
+                    break 'outer;
+                }
+
+                min_gallop = min_gallop.saturating_sub(1);
+                if left_wins < MIN_GALLOP && right_wins < MIN_GALLOP {
+                    break;
+                }
+            }
+            min_gallop += 2;
+        }
+        self.min_gallop = min_gallop.max(1);
+    }
+}
+
+#[test]
+fn test_tim_sort() {
+    use crate::utils::gen::Gen;
+
+    let mut gen = Gen::new(100);
+    // partially sorted logs: sorted blocks with random values and reversed blocks between them
+    for len in [63usize, 64, 65, 1000, 5000].iter() {
+        let mut data_set: Vec<(u32, usize)> = Vec::with_capacity(*len);
+        while data_set.len() < *len {
+            let block = (gen.below(300) as usize).min(*len - data_set.len());
+            let mut values: Vec<u32> = gen.uniform::<u32>(block).into_iter().map(|x| x % 500).collect();
+            match gen.below(3) {
+                0 => values.sort(),
+                1 => values.sort_by(|a, b| b.cmp(a)),
+                _ => {}
+            }
+            let start = data_set.len();
+            data_set.extend(values.into_iter().zip(start..));
+        }
+        let mut expected = data_set.clone();
+        expected.sort_by_key(|x| x.0);
+        let mut arr = data_set.clone();
+        TimSort.sort_by_key(&mut arr, |x| x.0);
+        assert_eq!(arr, expected);
+    }
+    assert_eq!(min_run(63), 63);
+    assert_eq!(min_run(64), 32);
+    assert_eq!(min_run(65), 33);
+    assert_eq!(min_run(2048), 32);
+    assert_eq!(min_run(2049), 33);
+}
@@ -0,0 +1,160 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/19 10:30
+//
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// benchmark runner, measured by monotonic clock
+/// the function is run `warmup` samples first without measuring, then `samples` samples
+/// are measured, each of them runs the function `iterations` times
+/// ```
+/// use algorithms_rs::utils::bench::Bench;
+///
+/// let data_set: Vec<u32> = (0..1000).rev().collect();
+/// let bench = Bench { samples: 5, iterations: 10, ..Bench::new() };
+/// // sort a fresh copy every time, cloning is not measured
+/// let result = bench.run_with_setup(|| data_set.clone(), |mut arr| arr.sort());
+/// assert_eq!(result.samples.len(), 5);
+/// assert!(result.min <= result.median && result.median <= result.max);
+/// println!("{}", result);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Bench {
+    /// samples run before measuring
+    pub warmup: u32,
+    /// samples measured
+    pub samples: u32,
+    /// function calls per sample
+    pub iterations: u32,
+}
+
+impl Bench {
+    /// create benchmark runner with 3 warmup samples and 10 samples of 1 iteration
+    pub fn new() -> Self {
+        Self {
+            warmup: 3,
+            samples: 10,
+            iterations: 1,
+        }
+    }
+
+    /// measure `func`, its return value is kept from being optimized out
+    pub fn run<F, R>(&self, mut func: F) -> BenchResult
+        where F: FnMut() -> R
+    {
+        self.run_with_setup(|| (), |_| func())
+    }
+
+    /// measure `func` with fresh input from `setup` for every call,
+    /// inputs of a sample are made before it's measured and outputs are dropped after,
+    /// so neither `setup` nor dropping is measured
+    pub fn run_with_setup<S, I, F, R>(&self, mut setup: S, mut func: F) -> BenchResult
+        where S: FnMut() -> I,
+              F: FnMut(I) -> R
+    {
+        let iterations = self.iterations.max(1);
+        let mut sample = || {
+            let inputs: Vec<I> = (0..iterations).map(|_| setup()).collect();
+            let mut outputs = Vec::with_capacity(inputs.len());
+            let start = Instant::now();
+            for input in inputs {
+                outputs.push(black_box(func(black_box(input))));
+            }
+            let elapsed = start.elapsed();
+            drop(outputs);
+            elapsed / iterations
+        };
+        for _ in 0..self.warmup {
+            sample();
+        }
+        let samples = (0..self.samples.max(1)).map(|_| sample()).collect();
+        BenchResult::new(samples, iterations)
+    }
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// statistics of benchmark, all durations are time of one function call
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// mean time of each sample in measured order
+    pub samples: Vec<Duration>,
+    /// function calls per sample
+    pub iterations: u32,
+    pub mean: Duration,
+    pub median: Duration,
+    /// sample standard deviation
+    pub stddev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl BenchResult {
+    fn new(samples: Vec<Duration>, iterations: u32) -> Self {
+        let mut sorted = samples.clone();
+        sorted.sort();
+        let len = sorted.len();
+        let median = (sorted[(len - 1) / 2] + sorted[len / 2]) / 2;
+        let mean = sorted.iter().sum::<Duration>() / len as u32;
+        let variance = if len > 1 {
+            sorted.iter()
+                .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>() / (len - 1) as f64
+        } else {
+            0.0
+        };
+        Self {
+            iterations,
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            min: sorted[0],
+            max: sorted[len - 1],
+            samples,
+        }
+    }
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mean: {:?} ± {:?}, median: {:?}, min: {:?}, max: {:?} ({} samples × {} iterations)",
+               self.mean, self.stddev, self.median, self.min, self.max, self.samples.len(), self.iterations)
+    }
+}
+
+#[test]
+fn test_bench() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let setups = Cell::new(0);
+    let bench = Bench { warmup: 2, samples: 4, iterations: 3 };
+    let result = bench.run_with_setup(|| setups.set(setups.get() + 1), |_| calls.set(calls.get() + 1));
+    assert_eq!(calls.get(), 18);
+    assert_eq!(setups.get(), 18);
+    assert_eq!(result.samples.len(), 4);
+
+    let result = BenchResult::new((1..=5).map(Duration::from_millis).collect(), 1);
+    assert_eq!(result.mean, Duration::from_millis(3));
+    assert_eq!(result.median, Duration::from_millis(3));
+    assert_eq!(result.min, Duration::from_millis(1));
+    assert_eq!(result.max, Duration::from_millis(5));
+    assert!((result.stddev.as_secs_f64() - 2.5f64.sqrt() / 1000.0).abs() < 1e-9);
+
+    let result = BenchResult::new(vec![Duration::from_millis(4), Duration::from_millis(2)], 1);
+    assert_eq!(result.median, Duration::from_millis(3));
+    assert_eq!(result.samples[0], Duration::from_millis(4));
+
+    // each input is fresh even though the function mutates it
+    let result = Bench::new().run_with_setup(|| vec![3, 1, 2], |mut arr| {
+        assert_eq!(arr, vec![3, 1, 2]);
+        arr.sort();
+        arr
+    });
+    assert!(result.min <= result.max);
+}
@@ -0,0 +1,186 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/21 10:05
+//
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Sub;
+
+thread_local! {
+    static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+    static SWAPS: Cell<u64> = const { Cell::new(0) };
+    static CLONES: Cell<u64> = const { Cell::new(0) };
+}
+
+fn increase(counter: &'static std::thread::LocalKey<Cell<u64>>) {
+    counter.with(|count| count.set(count.get() + 1));
+}
+
+/// wrapper counting operations on `T`, use it in place of `T` to analyze an algorithm
+/// every `==`, `<`, `cmp`... counts as one comparison and every `clone` as one clone,
+/// the counters are per thread, so tests running in parallel don't disturb each other
+///
+/// moving a value runs no code in Rust, so the wrapper itself can't see swaps, only swaps made by
+/// `Counted::swap` are counted. the algorithms of this crate exchange elements through
+/// `counted::swap`, which counts them too when the `count-swaps` feature is enabled. elements
+/// moved by copying or rotating, like merges of `MergeSort` and `TimSort`, are never counted
+/// ```
+/// use algorithms_rs::searching::binary;
+/// use algorithms_rs::utils::counted::{measure, Counted};
+///
+/// let data_set: Vec<Counted<u32>> = (0..1000).map(Counted).collect();
+/// let (found, report) = measure(|| binary::search(&data_set, &Counted(777)));
+/// assert_eq!(found, Some(777));
+/// assert!(report.comparisons <= 11);
+/// ```
+#[derive(Debug, Default)]
+pub struct Counted<T>(pub T);
+
+impl<T> Counted<T> {
+    /// return the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// swap `arr[a]` and `arr[b]` and count it
+    pub fn swap(arr: &mut [Counted<T>], a: usize, b: usize) {
+        increase(&SWAPS);
+        arr.swap(a, b);
+    }
+}
+
+impl<T> From<T> for Counted<T> {
+    fn from(value: T) -> Self {
+        Counted(value)
+    }
+}
+
+impl<T: Clone> Clone for Counted<T> {
+    fn clone(&self) -> Self {
+        increase(&CLONES);
+        Counted(self.0.clone())
+    }
+}
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        increase(&COMPARISONS);
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Counted<T> {}
+
+impl<T: PartialOrd> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        increase(&COMPARISONS);
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        increase(&COMPARISONS);
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Counted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// numbers of operations made on `Counted` values, and swaps made by `counted::swap`
+/// if the `count-swaps` feature is enabled
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Report {
+    pub comparisons: u64,
+    pub swaps: u64,
+    pub clones: u64,
+}
+
+impl Report {
+    /// return the operations counted in current thread so far
+    pub fn now() -> Self {
+        Self {
+            comparisons: COMPARISONS.with(Cell::get),
+            swaps: SWAPS.with(Cell::get),
+            clones: CLONES.with(Cell::get),
+        }
+    }
+}
+
+impl Sub for Report {
+    type Output = Report;
+
+    fn sub(self, other: Report) -> Report {
+        Report {
+            comparisons: self.comparisons - other.comparisons,
+            swaps: self.swaps - other.swaps,
+            clones: self.clones - other.clones,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "comparisons: {}, swaps: {}, clones: {}", self.comparisons, self.swaps, self.clones)
+    }
+}
+
+/// run `func` and return its result with the operations it made on `Counted` values,
+/// it can be nested
+pub fn measure<F, R>(func: F) -> (R, Report)
+    where F: FnOnce() -> R
+{
+    let start = Report::now();
+    let result = func();
+    (result, Report::now() - start)
+}
+
+/// swap `arr[a]` and `arr[b]`, algorithms call it in place of `slice::swap`,
+/// it's counted in `Report::swaps` only if the `count-swaps` feature is enabled,
+/// otherwise it's `slice::swap` and costs nothing more
+#[inline]
+pub fn swap<T>(arr: &mut [T], a: usize, b: usize) {
+    #[cfg(feature = "count-swaps")]
+    increase(&SWAPS);
+    arr.swap(a, b);
+}
+
+/// reverse `arr` by `counted::swap`
+pub fn reverse<T>(arr: &mut [T]) {
+    let len = arr.len();
+    for i in 0..len / 2 {
+        swap(arr, i, len - 1 - i);
+    }
+}
+
+#[test]
+fn test_counted() {
+    let a = Counted(1);
+    let b = Counted(2);
+    let ((), report) = measure(|| {
+        assert!(a < b);
+        assert!(a != b);
+        assert_eq!(a.cmp(&b), Ordering::Less);
+        let ((), inner) = measure(|| {
+            let _ = a.clone();
+        });
+        assert_eq!(inner, Report { comparisons: 0, swaps: 0, clones: 1 });
+    });
+    assert_eq!(report, Report { comparisons: 3, swaps: 0, clones: 1 });
+
+    let mut arr = vec![Counted(1), Counted(2)];
+    let ((), report) = measure(|| Counted::swap(&mut arr, 0, 1));
+    assert_eq!(report.swaps, 1);
+    assert_eq!(arr[0].0, 2);
+    assert_eq!(format!("{}", report), "comparisons: 0, swaps: 1, clones: 0");
+
+    // counters are per thread
+    let ((), report) = measure(|| {
+        std::thread::spawn(|| Counted(1) == Counted(1)).join().unwrap();
+    });
+    assert_eq!(report.comparisons, 0);
+}
@@ -0,0 +1,286 @@
+// Project algorithms-rs
+// Create by VenmoSnake 2020/8/20 09:45
+//
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use random::{Source, Value, Xorshift128Plus};
+
+use crate::searching::binary;
+
+/// reproducible dataset generator, the same seed always generates the same data,
+/// print `seed()` when a randomized test fails to replay it exactly
+/// ```
+/// use algorithms_rs::utils::gen::Gen;
+///
+/// let mut gen = Gen::new(100);
+/// let data_set: Vec<i32> = gen.uniform(10);
+/// assert_eq!(Gen::new(gen.seed()).uniform::<i32>(10), data_set);
+///
+/// let nearly = gen.nearly_sorted::<u32>(1000, 5);
+/// assert!(nearly.windows(2).filter(|w| w[0] > w[1]).count() <= 10);
+/// ```
+#[derive(Clone)]
+pub struct Gen {
+    seed: u64,
+    rng: Xorshift128Plus,
+}
+
+impl Gen {
+    /// create generator from seed
+    pub fn new(seed: u64) -> Self {
+        // xorshift must not start from all zero state
+        Self {
+            seed,
+            rng: Xorshift128Plus::new([seed, 0x9E37_79B9_7F4A_7C15]),
+        }
+    }
+
+    /// return the seed of generator
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// return one uniformly distributed value
+    pub fn value<T: Value>(&mut self) -> T {
+        self.rng.read()
+    }
+
+    /// return uniformly distributed integer in `[0, bound)`
+    /// # Panics
+    /// panics if `bound` is 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        // high half of the product is unbiased enough and needs no division
+        ((self.rng.read_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// return uniformly distributed float in `[0, 1)`
+    pub fn unit(&mut self) -> f64 {
+        self.rng.read_f64()
+    }
+
+    /// shuffle `arr` by Fisher-Yates, every permutation is equally likely
+    pub fn shuffle<T>(&mut self, arr: &mut [T]) {
+        for i in (1..arr.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            arr.swap(i, j);
+        }
+    }
+
+    /// uniformly distributed values over the whole range of `T`
+    pub fn uniform<T: Value>(&mut self, len: usize) -> Vec<T> {
+        (0..len).map(|_| self.value()).collect()
+    }
+
+    /// uniformly distributed values in ascending order
+    pub fn sorted<T: Value + Ord>(&mut self, len: usize) -> Vec<T> {
+        let mut arr = self.uniform(len);
+        arr.sort();
+        arr
+    }
+
+    /// uniformly distributed values in descending order
+    pub fn reverse<T: Value + Ord>(&mut self, len: usize) -> Vec<T> {
+        let mut arr = self.sorted(len);
+        arr.reverse();
+        arr
+    }
+
+    /// ascending values with `swaps` random pairs swapped
+    pub fn nearly_sorted<T: Value + Ord>(&mut self, len: usize, swaps: usize) -> Vec<T> {
+        let mut arr = self.sorted(len);
+        if len > 1 {
+            for _ in 0..swaps {
+                let i = self.below(len as u64) as usize;
+                let j = self.below(len as u64) as usize;
+                arr.swap(i, j);
+            }
+        }
+        arr
+    }
+
+    /// values from 0 to `max` drawn from zipf distribution with exponent `s`,
+    /// the probability of `k` is proportional to `1 / (k + 1)^s`, so small values are hot
+    /// # Panics
+    /// panics if `max` is 0
+    pub fn zipf(&mut self, len: usize, max: usize, s: f64) -> Vec<usize> {
+        assert!(max > 0, "max must be positive");
+        let mut cdf: Vec<f64> = (1..=max)
+            .scan(0.0, |sum, k| {
+                *sum += (k as f64).powf(-s);
+                Some(*sum)
+            })
+            .collect();
+        let total = cdf[max - 1];
+        cdf.iter_mut().for_each(|x| *x /= total);
+        (0..len)
+            .map(|_| {
+                let p = self.unit();
+                binary::partition_point(&cdf, |x| *x <= p).min(max - 1)
+            })
+            .collect()
+    }
+
+    /// values drawn uniformly from `0..unique`, so every value is repeated about `len / unique` times
+    /// # Panics
+    /// panics if `unique` is 0
+    pub fn few_unique(&mut self, len: usize, unique: usize) -> Vec<usize> {
+        (0..len).map(|_| self.below(unique as u64) as usize).collect()
+    }
+
+    /// random simple graph in the format of `g.txt`: "V E" in the first line then one edge "a b"
+    /// per line, without self loop and parallel edges
+    /// # Panics
+    /// panics if there are more edges than `vertex * (vertex - 1) / 2`
+    /// ```
+    /// use algorithms_rs::utils::gen::Gen;
+    ///
+    /// let text = Gen::new(7).graph(5, 4);
+    /// let lines: Vec<&str> = text.lines().collect();
+    /// assert_eq!(lines[0], "5 4");
+    /// assert_eq!(lines.len(), 5);
+    /// ```
+    pub fn graph(&mut self, vertex: usize, edge: usize) -> String {
+        let max = vertex * vertex.saturating_sub(1) / 2;
+        assert!(edge <= max, "a simple graph of {} vertexes has at most {} edges", vertex, max);
+        let edges: Vec<(usize, usize)> = if edge * 2 > max {
+            // dense graph: pick from all pairs
+            let mut pairs: Vec<(usize, usize)> = (0..vertex)
+                .flat_map(|a| (a + 1..vertex).map(move |b| (a, b)))
+                .collect();
+            self.shuffle(&mut pairs);
+            pairs.truncate(edge);
+            pairs
+        } else {
+            // sparse graph: retry on duplicated pair
+            let mut seen = HashSet::with_capacity(edge);
+            let mut pairs = Vec::with_capacity(edge);
+            while pairs.len() < edge {
+                let a = self.below(vertex as u64) as usize;
+                let b = self.below(vertex as u64) as usize;
+                if a != b && seen.insert((a.min(b), a.max(b))) {
+                    pairs.push((a, b));
+                }
+            }
+            pairs
+        };
+        let mut text = format!("{} {}\n", vertex, edge);
+        for (a, b) in edges {
+            writeln!(text, "{} {}", a, b).unwrap();
+        }
+        text
+    }
+}
+
+/// ascending from 0 to `len / 2` then descending back
+/// ```
+/// use algorithms_rs::utils::gen::organ_pipe;
+///
+/// assert_eq!(organ_pipe(7), vec![0, 1, 2, 3, 2, 1, 0]);
+/// ```
+pub fn organ_pipe(len: usize) -> Vec<usize> {
+    (0..len).map(|i| i.min(len - 1 - i)).collect()
+}
+
+/// ascending runs of length `period` from 0 to `period - 1`
+/// ```
+/// use algorithms_rs::utils::gen::sawtooth;
+///
+/// assert_eq!(sawtooth(7, 3), vec![0, 1, 2, 0, 1, 2, 0]);
+/// ```
+pub fn sawtooth(len: usize, period: usize) -> Vec<usize> {
+    (0..len).map(|i| i % period).collect()
+}
+
+/// McIlroy's "killer adversary": run `sort` over `len` items with a comparator which decides
+/// the values lazily so that every pivot is as bad as possible, and return the values it decided,
+/// sorting them again by the same deterministic `sort` takes the same worst path
+/// ```
+/// use algorithms_rs::sorting::Sorter;
+/// use algorithms_rs::sorting::quickly::{Pivot, QuickSort};
+/// use algorithms_rs::utils::gen::quicksort_killer;
+///
+/// let sorter = QuickSort::with_pivot(Pivot::MedianOfThree);
+/// let data_set = quicksort_killer(1000, |arr, compare| sorter.sort_by(arr, compare));
+/// assert_eq!(data_set.len(), 1000);
+/// ```
+pub fn quicksort_killer<S>(len: usize, sort: S) -> Vec<usize>
+    where S: FnOnce(&mut [usize], &mut dyn FnMut(&usize, &usize) -> Ordering)
+{
+    // items not compared yet are "gas", greater than every frozen ("solid") item
+    let gas = len;
+    let values = RefCell::new(vec![gas; len]);
+    let solid = Cell::new(0);
+    let candidate = Cell::new(0);
+    let mut items: Vec<usize> = (0..len).collect();
+    sort(&mut items, &mut |x, y| {
+        let mut values = values.borrow_mut();
+        if values[*x] == gas && values[*y] == gas {
+            let freeze = if *x == candidate.get() { *x } else { *y };
+            values[freeze] = solid.get();
+            solid.set(solid.get() + 1);
+        }
+        if values[*x] == gas {
+            candidate.set(*x);
+        } else if values[*y] == gas {
+            candidate.set(*y);
+        }
+        values[*x].cmp(&values[*y])
+    });
+    values.into_inner()
+}
+
+#[test]
+fn test_gen() {
+    let mut gen = Gen::new(42);
+    let mut replay = Gen::new(42);
+    assert_eq!(gen.uniform::<u64>(100), replay.uniform::<u64>(100));
+    assert_ne!(Gen::new(43).uniform::<u64>(10), Gen::new(42).uniform::<u64>(10));
+
+    let sorted: Vec<i64> = gen.sorted(500);
+    assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+    let reverse: Vec<i64> = gen.reverse(500);
+    assert!(reverse.windows(2).all(|w| w[0] >= w[1]));
+
+    assert!(gen.few_unique(1000, 4).iter().all(|x| *x < 4));
+    assert!((0..1000).all(|_| gen.below(3) < 3));
+
+    let mut arr: Vec<usize> = (0..100).collect();
+    gen.shuffle(&mut arr);
+    assert_ne!(arr, (0..100).collect::<Vec<_>>());
+    arr.sort();
+    assert_eq!(arr, (0..100).collect::<Vec<_>>());
+
+    // the first value is the most frequent, about 1 / H(100) ≈ 19%
+    let zipf = gen.zipf(10000, 100, 1.0);
+    assert!(zipf.iter().all(|x| *x < 100));
+    let hot = zipf.iter().filter(|x| **x == 0).count();
+    assert!(hot > 1500 && hot < 2400, "{}", hot);
+    assert!(zipf.iter().filter(|x| **x == 1).count() < hot);
+
+    assert_eq!(organ_pipe(0), vec![]);
+    assert_eq!(organ_pipe(4), vec![0, 1, 1, 0]);
+    assert_eq!(sawtooth(5, 2), vec![0, 1, 0, 1, 0]);
+}
+
+#[test]
+fn test_gen_graph() {
+    use crate::graph::{AdjacencyList, Graph};
+
+    let mut gen = Gen::new(100);
+    for (vertex, edge) in [(2, 1), (10, 9), (10, 45), (100, 300)].iter() {
+        let text = gen.graph(*vertex, *edge);
+        let path = std::env::temp_dir().join(format!("algorithms-rs-gen-{}-{}.txt", vertex, edge));
+        std::fs::write(&path, &text).unwrap();
+        // the loader rejects self loop and parallel edges
+        let graph = AdjacencyList::from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(graph.vertex(), *vertex);
+        assert_eq!(graph.edge(), *edge);
+        assert_eq!((0..*vertex).map(|v| graph.degree(v)).sum::<usize>(), 2 * edge);
+    }
+    assert_eq!(gen.graph(0, 0), "0 0\n");
+}
@@ -1,28 +1,33 @@
 // Project algorithms-rs
 // Create by VenmoSnake 2020/7/13 11:41
 //
-use std::time::SystemTime;
+use std::time::Instant;
 
 mod graph;
+pub mod bench;
+pub mod counted;
+pub mod gen;
 
 /// calculate function execute time
+/// deprecated: it only prints the total time of all calls, use `bench::Bench` to get statistics
 /// ```no_run
-/// use random::Source;
+/// # #![allow(deprecated)]
+/// use algorithms_rs::searching::liner;
+/// use algorithms_rs::utils::gen::Gen;
 /// use algorithms_rs::utils::time_it;
 ///
-/// let mut gen = random::default().seed([0, 100]);
-/// let data_set: Vec<i32> = gen.iter().take(30).collect();
+/// let data_set: Vec<i32> = Gen::new(100).uniform(30);
 /// time_it(|| {
 ///     liner::search(&data_set, &4); // data_set is moved
 /// }, 1000);
 /// ```
+#[deprecated(note = "use `utils::bench::Bench` instead")]
 pub fn time_it<T>(func: T, times: u32)
     where T: FnOnce() + Copy
 {
-    let start = SystemTime::now();
+    let start = Instant::now();
     for _i in 0..times {
         func();
     }
-    let end = SystemTime::now();
-    println!("function execute time: `{:?}`", end.duration_since(start).unwrap())
+    println!("function execute time: `{:?}`", start.elapsed())
 }